@@ -23,11 +23,12 @@
 use std::fmt;
 
 use cgmath::{EuclideanSpace, Point2, Point3};
-use cgmath::{VectorSpace, Array, Vector2, Vector3};
+use cgmath::{VectorSpace, InnerSpace, Array, Vector2, Vector3};
 use cgmath::{BaseNum, BaseFloat, ElementWise};
 
-use {Ray2, Ray3, Plane};
-use bound::{Bound, Relation};
+use {HalfPlane, Line, Ray, Ray2, Ray3, Line2, Line3, Overlap, Plane, Sphere};
+use bound::{Bound, Bound2, Relate, Relation};
+use span::{RaySpan, Span};
 use intersect::Intersect;
 
 pub trait MinMax {
@@ -73,6 +74,74 @@ impl<S> MinMax for Point3<S>
     }
 }
 
+/// Which points on the boundary of a box count as part of it.
+///
+/// The `_with` box queries take a `Boundary`, applied to every box involved:
+/// containment, overlap, relations between boxes, and the parts of rays and
+/// segments inside a box. Without one, containment of points is half-open,
+/// while overlap, `Overlap`, rays and segments treat boxes as closed.
+/// `Relate` then reports a box as `In` only if it lies inside the open box,
+/// and as `Out` only if it lies outside of the closed box.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Boundary<S> {
+    /// The box includes all of its boundary, as for physics.
+    Closed,
+    /// The box includes the faces at its min corner but not those at its
+    /// max corner, so boxes tiling space do not share any point.
+    HalfOpen,
+    /// The box includes none of its boundary.
+    Open,
+    /// The box is closed and grown by a tolerance on every side, to accept
+    /// points slightly outside of it due to rounding. A negative tolerance
+    /// shrinks the box instead.
+    Tolerance(S),
+}
+
+impl<S: BaseNum> Boundary<S> {
+    /// Whether `x` lies in the range `[lo, hi]` with these boundaries.
+    fn includes(&self, lo: S, hi: S, x: S) -> bool {
+        match *self {
+            Boundary::Closed => lo <= x && x <= hi,
+            Boundary::HalfOpen => lo <= x && x < hi,
+            Boundary::Open => lo < x && x < hi,
+            Boundary::Tolerance(e) => lo <= x + e && x <= hi + e,
+        }
+    }
+
+    /// Whether the ranges `[a_lo, a_hi]` and `[b_lo, b_hi]` share any point
+    /// with these boundaries.
+    fn overlaps(&self, a_lo: S, a_hi: S, b_lo: S, b_hi: S) -> bool {
+        match *self {
+            Boundary::Closed => a_lo <= b_hi && b_lo <= a_hi,
+            Boundary::HalfOpen | Boundary::Open => {
+                a_lo < a_hi && b_lo < b_hi && a_lo < b_hi && b_lo < a_hi
+            }
+            Boundary::Tolerance(e) => a_lo <= b_hi + e && b_lo <= a_hi + e,
+        }
+    }
+
+    /// Whether every point of the range `[a_lo, a_hi]` lies in the range
+    /// `[b_lo, b_hi]` with these boundaries. Empty ranges lie nowhere.
+    fn within(&self, a_lo: S, a_hi: S, b_lo: S, b_hi: S) -> bool {
+        match *self {
+            Boundary::Closed => a_lo <= a_hi && b_lo <= a_lo && a_hi <= b_hi,
+            Boundary::HalfOpen | Boundary::Open => a_lo < a_hi && b_lo <= a_lo && a_hi <= b_hi,
+            Boundary::Tolerance(e) => a_lo <= a_hi && b_lo <= a_lo + e && a_hi <= b_hi + e,
+        }
+    }
+
+    /// Whether the faces at the min and max corner of a box belong to it,
+    /// and how far the box is grown.
+    fn faces(&self) -> (bool, bool, S) {
+        match *self {
+            Boundary::Closed => (true, true, S::zero()),
+            Boundary::HalfOpen => (true, false, S::zero()),
+            Boundary::Open => (false, false, S::zero()),
+            Boundary::Tolerance(e) => (true, true, e),
+        }
+    }
+}
+
 pub trait Aabb<S: BaseNum, V: VectorSpace<Scalar=S> + ElementWise + Array<Element=S>, P: EuclideanSpace<Scalar=S, Diff=V>>: Sized {
     /// Create a new AABB using two points as opposing corners.
     fn new(p1: P, p2: P) -> Self;
@@ -101,7 +170,230 @@ pub trait Aabb<S: BaseNum, V: VectorSpace<Scalar=S> + ElementWise + Array<Elemen
     /// Tests whether a point is cointained in the box, inclusive for min corner
     /// and exclusive for the max corner.
     #[inline]
-    fn contains(&self, p: P) -> bool;
+    fn contains(&self, p: P) -> bool {
+        self.contains_with(p, Boundary::HalfOpen)
+    }
+
+    /// Tests whether a point is contained in the box, with the given
+    /// treatment of the boundary.
+    fn contains_with(&self, p: P, boundary: Boundary<S>) -> bool {
+        let (min, max) = (self.min(), self.max());
+        (0..axes::<S, P>()).all(|i| boundary.includes(min[i], max[i], p[i]))
+    }
+
+    /// Return the point of the box nearest to `p`, which is `p` itself if it
+    /// lies inside.
+    fn closest_point(&self, p: P) -> P {
+        let (min, max) = (self.min(), self.max());
+        let mut q = p;
+        for i in 0..axes::<S, P>() {
+            q[i] = p[i].partial_max(min[i]).partial_min(max[i]);
+        }
+        q
+    }
+
+    /// Return the smallest box containing both this box and another one.
+    fn union(&self, other: &Self) -> Self {
+        let (mut min, mut max) = (self.min(), self.max());
+        let (other_min, other_max) = (other.min(), other.max());
+        for i in 0..axes::<S, P>() {
+            min[i] = min[i].partial_min(other_min[i]);
+            max[i] = max[i].partial_max(other_max[i]);
+        }
+        Aabb::new(min, max)
+    }
+
+    /// Tests whether this box and another one share any point, including
+    /// when they only touch.
+    #[inline]
+    fn intersects(&self, other: &Self) -> bool {
+        self.intersects_with(other, Boundary::Closed)
+    }
+
+    /// Tests whether this box and another one share any point, with the
+    /// given treatment of their boundaries. Boxes with a tolerance may be
+    /// that far apart.
+    fn intersects_with(&self, other: &Self, boundary: Boundary<S>) -> bool {
+        let (min, max) = (self.min(), self.max());
+        let (other_min, other_max) = (other.min(), other.max());
+        (0..axes::<S, P>()).all(|i| boundary.overlaps(min[i], max[i], other_min[i], other_max[i]))
+    }
+
+    /// Return the box where this box and another one overlap, or `None` if
+    /// they do not. Boxes that only touch overlap in a box of zero width.
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        if !self.intersects(other) {
+            return None;
+        }
+        let (mut min, mut max) = (self.min(), self.max());
+        let (other_min, other_max) = (other.min(), other.max());
+        for i in 0..axes::<S, P>() {
+            min[i] = min[i].partial_max(other_min[i]);
+            max[i] = max[i].partial_min(other_max[i]);
+        }
+        Some(Aabb::new(min, max))
+    }
+
+    /// Return the box grown by `margin` on every side, or shrunk if `margin`
+    /// is negative.
+    fn expand(&self, margin: S) -> Self {
+        let (mut min, mut max) = (self.min(), self.max());
+        for i in 0..axes::<S, P>() {
+            min[i] = min[i] - margin;
+            max[i] = max[i] + margin;
+        }
+        Aabb::new(min, max)
+    }
+
+    /// Return the squared distance from the box to a point, which is zero if
+    /// the point lies inside.
+    fn distance2(&self, p: P) -> S {
+        let (min, max) = (self.min(), self.max());
+        (0..axes::<S, P>()).fold(S::zero(), |sum, i| {
+            let d = if p[i] < min[i] {
+                min[i] - p[i]
+            } else if p[i] > max[i] {
+                p[i] - max[i]
+            } else {
+                S::zero()
+            };
+            sum + d * d
+        })
+    }
+
+    /// Return the index of the axis along which the box is the largest. Ties
+    /// go to the lower axis.
+    fn longest_axis(&self) -> usize {
+        let dim = self.dim();
+        (1..axes::<S, P>()).fold(0, |best, i| if dim[i] > dim[best] { i } else { best })
+    }
+
+    /// Split the box in two along `axis` at the coordinate `at`, which is
+    /// clamped to the box. Returns the lower half first.
+    fn split(&self, axis: usize, at: S) -> (Self, Self) {
+        let (min, max) = (self.min(), self.max());
+        let at = at.partial_max(min[axis]).partial_min(max[axis]);
+        let (mut lower_max, mut upper_min) = (max, min);
+        lower_max[axis] = at;
+        upper_min[axis] = at;
+        (Aabb::new(min, lower_max), Aabb::new(upper_min, max))
+    }
+
+    /// Return the parts of this box outside of another one, as at most two
+    /// boxes per axis that do not overlap each other except on their
+    /// boundaries. A box only touching this one leaves it whole.
+    fn subtract(&self, other: &Self) -> Vec<Self> {
+        let clip = match self.intersection(other) {
+            Some(clip) => clip,
+            None => return vec![Aabb::new(self.min(), self.max())],
+        };
+        let (clip_min, clip_max) = (clip.min(), clip.max());
+        let (mut min, mut max) = (self.min(), self.max());
+        if (0..axes::<S, P>()).any(|i| clip_min[i] == clip_max[i] && min[i] < max[i]) {
+            return vec![Aabb::new(min, max)];
+        }
+        let mut parts = Vec::new();
+        for i in 0..axes::<S, P>() {
+            if min[i] < clip_min[i] {
+                let mut part_max = max;
+                part_max[i] = clip_min[i];
+                parts.push(Aabb::new(min, part_max));
+                min[i] = clip_min[i];
+            }
+            if clip_max[i] < max[i] {
+                let mut part_min = min;
+                part_min[i] = clip_max[i];
+                parts.push(Aabb::new(part_min, max));
+                max[i] = clip_max[i];
+            }
+        }
+        parts
+    }
+
+    /// Return the area of the boundary of the box, which is its perimeter
+    /// for a two-dimensional box.
+    fn surface_area(&self) -> S {
+        let dim = self.dim();
+        let n = axes::<S, P>();
+        let faces = (0..n).fold(S::zero(), |sum, i| {
+            sum + (0..n).filter(|&j| j != i).fold(S::one(), |area, j| area * dim[j])
+        });
+        faces + faces
+    }
+
+    /// Find the first point where a ray hits the boundary of the box. If the
+    /// ray starts inside the box, this is where it leaves the box. The box is
+    /// closed, so rays grazing it hit it.
+    #[inline]
+    fn intersect_ray(&self, ray: &Ray<S, P, V>) -> Option<P>
+        where S: BaseFloat
+    {
+        self.intersect_ray_with(ray, Boundary::Closed)
+    }
+
+    /// Find the first point where a ray hits the boundary of the box, with
+    /// the given treatment of the boundary. Rays only grazing an open box
+    /// miss it.
+    fn intersect_ray_with(&self, ray: &Ray<S, P, V>, boundary: Boundary<S>) -> Option<P>
+        where S: BaseFloat
+    {
+        let range = clip_slabs(ray.origin, ray.direction, self.min(), self.max(),
+                               S::neg_infinity(), S::infinity(), boundary);
+        if ray.direction.is_zero() {
+            return range.map(|_| ray.origin);
+        }
+        ray_hit(range).map(|t| ray.origin + ray.direction * t)
+    }
+
+    /// Find the first point of a segment that lies within the closed box. If
+    /// the segment starts inside the box, this is the segment's origin.
+    #[inline]
+    fn intersect_line(&self, line: &Line<S, V, P>) -> Option<P>
+        where S: BaseFloat
+    {
+        self.intersect_line_with(line, Boundary::Closed)
+    }
+
+    /// Find the first point of a segment that lies within the box, with the
+    /// given treatment of the boundary. For boxes not including all of their
+    /// boundary, this may be a point on the boundary where the segment
+    /// enters the box.
+    fn intersect_line_with(&self, line: &Line<S, V, P>, boundary: Boundary<S>) -> Option<P>
+        where S: BaseFloat
+    {
+        let d = line.dest - line.origin;
+        clip_slabs(line.origin, d, self.min(), self.max(), S::zero(), S::one(), boundary)
+            .map(|(t, _)| line.origin + d * t)
+    }
+
+    /// Find the span of ray parameters at or after the origin for which the
+    /// ray is inside of the box, with the given treatment of the boundary.
+    /// The span includes its ends even if they lie on a boundary that is not
+    /// part of the box.
+    fn ray_span_with(&self, ray: &Ray<S, P, V>, boundary: Boundary<S>) -> Option<Span<S>>
+        where S: BaseFloat
+    {
+        clip_slabs(ray.origin, ray.direction, self.min(), self.max(),
+                   S::zero(), S::infinity(), boundary)
+            .map(|(t0, t1)| Span::new(t0, t1))
+    }
+
+    /// Find the spatial relation of this box to another one, with the given
+    /// treatment of the boundaries of both. This box is `In` if all of its
+    /// points belong to the other box, and `Out` if none of them do, which
+    /// includes the case of a box that has no points at all.
+    fn relate_with(&self, other: &Self, boundary: Boundary<S>) -> Relation {
+        let (min, max) = (self.min(), self.max());
+        let (other_min, other_max) = (other.min(), other.max());
+        let axes = 0..axes::<S, P>();
+        if axes.clone().any(|i| !boundary.overlaps(min[i], max[i], other_min[i], other_max[i])) {
+            Relation::Out
+        } else if axes.clone().all(|i| boundary.within(min[i], max[i], other_min[i], other_max[i])) {
+            Relation::In
+        } else {
+            Relation::Cross
+        }
+    }
 
     /// Returns a new AABB that is grown to include the given point.
     fn grow(&self, p: P) -> Self
@@ -170,14 +462,6 @@ impl<S: BaseNum> Aabb<S, Vector2<S>, Point2<S>> for Aabb2<S> {
 
     #[inline]
     fn max(&self) -> Point2<S> { self.max }
-
-    #[inline]
-    fn contains(&self, p: Point2<S>) -> bool {
-        let v_min = p - self.min();
-        let v_max = self.max() - p;
-        v_min.x >= S::zero() && v_min.y >= S::zero() &&
-        v_max.x >  S::zero() && v_max.y >  S::zero()
-    }
 }
 
 impl<S: BaseNum> fmt::Debug for Aabb2<S> {
@@ -231,14 +515,6 @@ impl<S: BaseNum> Aabb<S, Vector3<S>, Point3<S>> for Aabb3<S> {
 
     #[inline]
     fn max(&self) -> Point3<S> { self.max }
-
-    #[inline]
-    fn contains(&self, p: Point3<S>) -> bool {
-        let v_min = p - self.min();
-        let v_max = self.max() - p;
-        v_min.x >= S::zero() && v_min.y >= S::zero() && v_min.z >= S::zero() &&
-        v_max.x >  S::zero() && v_max.y >  S::zero() && v_max.z >  S::zero()
-    }
 }
 
 impl<S: BaseNum> fmt::Debug for Aabb3<S> {
@@ -247,93 +523,238 @@ impl<S: BaseNum> fmt::Debug for Aabb3<S> {
     }
 }
 
-impl<S: BaseFloat> Intersect<Option<Point2<S>>> for (Ray2<S>, Aabb2<S>) {
-    fn intersection(&self) -> Option<Point2<S>> {
-        let (ref ray, ref aabb) = *self;
-
-        let mut tmin = S::neg_infinity();
-        let mut tmax = S::infinity();
-
-        if ray.direction.x != S::zero() {
-            let tx1 = (aabb.min.x - ray.origin.x) / ray.direction.x;
-            let tx2 = (aabb.max.x - ray.origin.x) / ray.direction.x;
-            tmin = tmin.max(tx1.min(tx2));
-            tmax = tmax.min(tx1.max(tx2));
-        }
-
-        if ray.direction.y != S::zero() {
-            let ty1 = (aabb.min.y - ray.origin.y) / ray.direction.y;
-            let ty2 = (aabb.max.y - ray.origin.y) / ray.direction.y;
-            tmin = tmin.max(ty1.min(ty2));
-            tmax = tmax.min(ty1.max(ty2));
-        }
+/// The number of dimensions of a point or vector type.
+#[inline]
+pub fn axes<S: BaseNum, A: Array<Element=S>>() -> usize {
+    A::from_value(S::one()).sum().to_usize().unwrap()
+}
 
-        if tmin < S::zero() && tmax < S::zero() {
-            None
+/// Clip the line `origin + t * dir` to the slabs of the box `[min, max]`,
+/// starting from the range `[t0, t1]`, with the given treatment of the
+/// boundary of the box. Axes along which the line does not move only check
+/// that the origin lies between the slabs, so no infinities are involved.
+/// The range is returned with its ends, even if they only lie on the
+/// boundary, but is `None` if the line has no point inside of the box.
+pub fn clip_slabs<S, P, V>(origin: P, dir: V, min: P, max: P, mut t0: S, mut t1: S,
+                           boundary: Boundary<S>) -> Option<(S, S)>
+    where S: BaseFloat,
+          P: Array<Element=S>,
+          V: Array<Element=S>
+{
+    let (min_face, max_face, e) = boundary.faces();
+    // whether the ends of the range are excluded
+    let (mut open0, mut open1) = (false, false);
+    for i in 0..axes::<S, P>() {
+        if origin[i].is_nan() || dir[i].is_nan() || min[i].is_nan() || max[i].is_nan() {
+            return None;
         }
-        else if tmax >= tmin {
-            if tmin >= S::zero() {
-                Some(Point2::new(ray.origin.x + ray.direction.x * tmin,
-                                 ray.origin.y + ray.direction.y * tmin))
+        if dir[i] == S::zero() {
+            if !boundary.includes(min[i], max[i], origin[i]) {
+                return None;
             }
-            else {
-                Some(Point2::new(ray.origin.x + ray.direction.x * tmax,
-                                 ray.origin.y + ray.direction.y * tmax))
+        } else {
+            let a = (min[i] - e - origin[i]) / dir[i];
+            let b = (max[i] + e - origin[i]) / dir[i];
+            let (enter, exit, enter_face, exit_face) = if dir[i] > S::zero() {
+                (a, b, min_face, max_face)
+            } else {
+                (b, a, max_face, min_face)
+            };
+            if enter > t0 {
+                t0 = enter;
+                open0 = !enter_face;
+            } else if enter == t0 && !enter_face {
+                open0 = true;
+            }
+            if exit < t1 {
+                t1 = exit;
+                open1 = !exit_face;
+            } else if exit == t1 && !exit_face {
+                open1 = true;
             }
-        }
-        else {
-            None
         }
     }
+    if t0 < t1 || (t0 == t1 && !open0 && !open1) { Some((t0, t1)) } else { None }
 }
 
+/// The ray parameter of the first point on the boundary of a box hit by a
+/// ray, given the range of the ray inside the box.
+fn ray_hit<S: BaseFloat>(range: Option<(S, S)>) -> Option<S> {
+    match range {
+        Some((t0, _)) if t0 >= S::zero() => Some(t0),
+        Some((_, t1)) if t1 >= S::zero() => Some(t1),
+        _ => None,
+    }
+}
+
+/// Finds the first point where the ray hits the boundary of the box. If the
+/// ray starts inside the box, this is where it leaves the box.
+impl<S: BaseFloat> Intersect<Option<Point2<S>>> for (Ray2<S>, Aabb2<S>) {
+    #[inline]
+    fn intersection(&self) -> Option<Point2<S>> { self.1.intersect_ray(&self.0) }
+}
+
+/// Finds the first point of the segment that lies within the box. If the
+/// segment starts inside the box, this is the segment's origin.
+impl<S: BaseFloat> Intersect<Option<Point2<S>>> for (Line2<S>, Aabb2<S>) {
+    #[inline]
+    fn intersection(&self) -> Option<Point2<S>> { self.1.intersect_line(&self.0) }
+}
+
+/// Finds the first point where the ray hits the boundary of the box. If the
+/// ray starts inside the box, this is where it leaves the box.
 impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Ray3<S>, Aabb3<S>) {
-    fn intersection(&self) -> Option<Point3<S>> {
-        let (ref ray, ref aabb) = *self;
+    #[inline]
+    fn intersection(&self) -> Option<Point3<S>> { self.1.intersect_ray(&self.0) }
+}
 
-        let inv_dir = Vector3::new(S::one(), S::one(), S::one()).div_element_wise(ray.direction);
+/// Finds the first point of the segment that lies within the box. If the
+/// segment starts inside the box, this is the segment's origin.
+impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Line3<S>, Aabb3<S>) {
+    #[inline]
+    fn intersection(&self) -> Option<Point3<S>> { self.1.intersect_line(&self.0) }
+}
 
-        let mut t1 = (aabb.min.x - ray.origin.x) * inv_dir.x;
-        let mut t2 = (aabb.max.x - ray.origin.x) * inv_dir.x;
+impl<S: BaseFloat> RaySpan<S, Ray2<S>> for Aabb2<S> {
+    #[inline]
+    fn ray_span(&self, ray: &Ray2<S>) -> Option<Span<S>> {
+        self.ray_span_with(ray, Boundary::Closed)
+    }
+}
 
-        let mut tmin = t1.min(t2);
-        let mut tmax = t1.max(t2);
+impl<S: BaseFloat> RaySpan<S, Ray3<S>> for Aabb3<S> {
+    #[inline]
+    fn ray_span(&self, ray: &Ray3<S>) -> Option<Span<S>> {
+        self.ray_span_with(ray, Boundary::Closed)
+    }
+}
 
-        for i in 1..3 {
-            t1 = (aabb.min[i] - ray.origin[i]) * inv_dir[i];
-            t2 = (aabb.max[i] - ray.origin[i]) * inv_dir[i];
+/// Finds the box where two boxes overlap. Boxes that only touch overlap in
+/// a box of zero width.
+impl<S: BaseNum> Intersect<Option<Aabb2<S>>> for (Aabb2<S>, Aabb2<S>) {
+    #[inline]
+    fn intersection(&self) -> Option<Aabb2<S>> { Aabb::intersection(&self.0, &self.1) }
+}
 
-            tmin = tmin.max(t1.min(t2));
-            tmax = tmax.min(t1.max(t2));
-        }
+/// Finds the box where two boxes overlap. Boxes that only touch overlap in
+/// a box of zero width.
+impl<S: BaseNum> Intersect<Option<Aabb3<S>>> for (Aabb3<S>, Aabb3<S>) {
+    #[inline]
+    fn intersection(&self) -> Option<Aabb3<S>> { Aabb::intersection(&self.0, &self.1) }
+}
 
-        if tmin < S::zero() && tmax < S::zero() {
-            None
-        } else if tmax >= tmin {
-            if tmin >= S::zero() {
-                Some(Point3::new(ray.origin.x + ray.direction.x * tmin,
-                                 ray.origin.y + ray.direction.y * tmin,
-                                 ray.origin.z + ray.direction.z * tmin))
-            } else {
-                Some(Point3::new(ray.origin.x + ray.direction.x * tmax,
-                                 ray.origin.y + ray.direction.y * tmax,
-                                 ray.origin.z + ray.direction.z * tmax))
-            }
-        } else {
-            None
+/// Relate the box `[a_min, a_max]` to the box `[b_min, b_max]` as by `Relate`.
+fn relate_boxes<S: BaseNum, P: Array<Element=S>>(a_min: P, a_max: P, b_min: P, b_max: P) -> Relation {
+    let axes = 0..axes::<S, P>();
+    if axes.clone().any(|i| !Boundary::Closed.overlaps(a_min[i], a_max[i], b_min[i], b_max[i])) {
+        Relation::Out
+    } else if axes.clone().all(|i| b_min[i] < a_min[i] && a_max[i] < b_max[i]) {
+        Relation::In
+    } else {
+        Relation::Cross
+    }
+}
+
+impl<S: BaseNum> Relate<Aabb2<S>> for Aabb2<S> {
+    fn relate(&self, other: &Aabb2<S>) -> Relation {
+        relate_boxes(self.min, self.max, other.min, other.max)
+    }
+}
+
+impl<S: BaseNum> Relate<Aabb3<S>> for Aabb3<S> {
+    fn relate(&self, other: &Aabb3<S>) -> Relation {
+        relate_boxes(self.min, self.max, other.min, other.max)
+    }
+}
+
+impl<S: BaseNum> Relate<Aabb2<S>> for Point2<S> {
+    fn relate(&self, other: &Aabb2<S>) -> Relation {
+        relate_boxes(*self, *self, other.min, other.max)
+    }
+}
+
+impl<S: BaseNum> Relate<Aabb3<S>> for Point3<S> {
+    fn relate(&self, other: &Aabb3<S>) -> Relation {
+        relate_boxes(*self, *self, other.min, other.max)
+    }
+}
+
+impl<S: BaseFloat + 'static> Relate<Sphere<S>> for Aabb3<S> {
+    fn relate(&self, other: &Sphere<S>) -> Relation {
+        if !self.overlaps(other) {
+            return Relation::Out;
         }
+        let c = other.center;
+        let far = |min: S, max: S, c: S| (c - min).abs().max((max - c).abs());
+        let v = Vector3::new(far(self.min.x, self.max.x, c.x), far(self.min.y, self.max.y, c.y),
+                             far(self.min.z, self.max.z, c.z));
+        if v.magnitude2() < other.radius * other.radius { Relation::In } else { Relation::Cross }
     }
 }
 
+/// Classify the signed distance `s` of a box center to a plane or line,
+/// given the box's projected radius `r` onto the normal.
+#[inline]
+fn relate_center_extent<S: BaseFloat>(s: S, r: S) -> Relation {
+    if s > r {
+        Relation::In
+    } else if s < -r {
+        Relation::Out
+    } else {
+        Relation::Cross
+    }
+}
+
+/// Classify the box `[min, max]` against the plane or line `n · p - d = 0`
+/// by the signed distance of its center and its extents projected onto `n`.
+#[inline]
+fn relate_box<S, P>(min: P, max: P, n: P::Diff, d: S) -> Relation
+    where S: BaseFloat,
+          P: EuclideanSpace<Scalar=S>,
+          P::Diff: InnerSpace<Scalar=S> + Array<Element=S>
+{
+    let half = S::one() / (S::one() + S::one());
+    let c = (min.to_vec() + max.to_vec()) * half;
+    let e = (max - min) * half;
+    let mut r = S::zero();
+    for i in 0..axes::<S, P::Diff>() {
+        r = r + e[i] * n[i].abs();
+    }
+    relate_center_extent(n.dot(c) - d, r)
+}
+
+impl<S: BaseFloat> Aabb2<S> {
+    /// Classify the box against the line `n.x * x + n.y * y - d = 0`, where
+    /// the side the normal points to is inside.
+    ///
+    /// See _Real-Time Collision Detection_, p. 161 - 164
+    pub fn relate_line(&self, n: Vector2<S>, d: S) -> Relation {
+        relate_box(self.min, self.max, n, d)
+    }
+}
+
+impl<S: BaseFloat + 'static> Bound2<S> for Aabb2<S> {
+    #[inline]
+    fn relate_half_plane(self, half_plane: HalfPlane<S>) -> Relation {
+        self.relate_line(half_plane.n, half_plane.d)
+    }
+}
+
+impl<S: BaseFloat> Aabb3<S> {
+    /// Classify many boxes against the same plane at once, replacing the
+    /// contents of `relations` with one entry per box.
+    pub fn relate_plane_batch(boxes: &[Aabb3<S>], plane: Plane<S>, relations: &mut Vec<Relation>) {
+        relations.clear();
+        relations.extend(boxes.iter().map(|b| relate_box(b.min, b.max, plane.n, plane.d)));
+    }
+}
+
+/// Classifies the box using its center and extents, so only the corner
+/// nearest to the plane is effectively tested.
+///
+/// See _Real-Time Collision Detection_, p. 161 - 164
 impl<S: BaseFloat + 'static> Bound<S> for Aabb3<S> {
     fn relate_plane(self, plane: Plane<S>) -> Relation {
-        let corners = self.to_corners();
-        let first = corners[0].relate_plane(plane);
-        for p in corners[1..].iter() {
-            if p.relate_plane(plane) != first {
-                return Relation::Cross;
-            }
-        }
-        first
+        relate_box(self.min, self.max, plane.n, plane.d)
     }
 }
@@ -15,10 +15,10 @@
 
 //! Generic spatial bounds.
 
-use Plane;
+use {Frustum, Frustum2, HalfPlane, Plane};
 use cgmath::{Matrix4};
 use cgmath::BaseFloat;
-use cgmath::{EuclideanSpace, Point3};
+use cgmath::{Point2, Point3};
 
 /// Spatial relation between two objects.
 #[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialOrd, PartialEq)]
@@ -46,12 +46,51 @@ pub trait Bound<S: BaseFloat + 'static>: Sized + Copy {
     }
 }
 
+/// Generic two-dimensional bound.
+pub trait Bound2<S: BaseFloat + 'static>: Sized + Copy {
+    /// Classify the spatial relation with a half-plane.
+    fn relate_half_plane(self, half_plane: HalfPlane<S>) -> Relation;
+}
+
+/// Spatial relation of an object to another one.
+///
+/// The relation is `In` when the object lies strictly inside of the other
+/// one, `Out` when the two do not even touch, and `Cross` otherwise. This
+/// matches the classification of bounds against planes and frusta, which
+/// are related by way of `Bound`. A box is thus `In` when it lies inside of
+/// the open other box, but `Out` only when it lies outside of the closed
+/// one; `Aabb::relate_with` relates boxes with a single treatment of their
+/// boundaries instead.
+pub trait Relate<Other> {
+    fn relate(&self, other: &Other) -> Relation;
+}
+
+impl<S: BaseFloat + 'static, B: Bound<S>> Relate<Plane<S>> for B {
+    #[inline]
+    fn relate(&self, other: &Plane<S>) -> Relation { self.relate_plane(*other) }
+}
+
+impl<S: BaseFloat + 'static, B: Bound<S>> Relate<Frustum<S>> for B {
+    #[inline]
+    fn relate(&self, other: &Frustum<S>) -> Relation { other.contains(*self) }
+}
+
+impl<S: BaseFloat + 'static, B: Bound2<S>> Relate<HalfPlane<S>> for B {
+    #[inline]
+    fn relate(&self, other: &HalfPlane<S>) -> Relation { self.relate_half_plane(*other) }
+}
+
+impl<S: BaseFloat + 'static, B: Bound2<S>> Relate<Frustum2<S>> for B {
+    #[inline]
+    fn relate(&self, other: &Frustum2<S>) -> Relation { other.contains(*self) }
+}
+
 impl<S: BaseFloat + 'static> Bound<S> for Point3<S> {
     fn relate_plane(self, plane: Plane<S>) -> Relation {
-        let dist = self.dot(plane.n);
-        if dist > plane.d {
+        let dist = plane.distance(self);
+        if dist > S::zero() {
             Relation::In
-        }else if dist < plane.d {
+        }else if dist < S::zero() {
             Relation::Out
         }else {
             Relation::Cross
@@ -68,3 +107,16 @@ impl<S: BaseFloat + 'static> Bound<S> for Point3<S> {
         }
     }
 }
+
+impl<S: BaseFloat + 'static> Bound2<S> for Point2<S> {
+    fn relate_half_plane(self, half_plane: HalfPlane<S>) -> Relation {
+        let dist = half_plane.distance(self);
+        if dist > S::zero() {
+            Relation::In
+        } else if dist < S::zero() {
+            Relation::Out
+        } else {
+            Relation::Cross
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cascaded shadow map frustum splitting
+
+use cgmath::{BaseFloat, EuclideanSpace, InnerSpace};
+use cgmath::{Matrix4, Ortho, Point3, Vector3};
+
+use {Frustum, FrustumPoints};
+
+/// How to distribute the split distances of a set of cascades.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SplitScheme<S> {
+    /// Splits evenly spaced in depth.
+    Uniform,
+    /// Splits in geometric progression from near to far.
+    Logarithmic,
+    /// A blend of the logarithmic and uniform schemes, as proposed for
+    /// parallel-split shadow maps. A `lambda` of `1` is fully logarithmic,
+    /// and `0` is fully uniform.
+    Practical(S),
+}
+
+impl<S: BaseFloat> SplitScheme<S> {
+    /// Compute the `count + 1` view distances bounding `count` cascades
+    /// between `near` and `far`.
+    pub fn distances(&self, near: S, far: S, count: usize) -> Vec<S> {
+        let n = S::from(count).unwrap();
+        (0..count + 1).map(|i| {
+            if i == 0 { return near; }
+            if i == count { return far; }
+            let f = S::from(i).unwrap() / n;
+            let uniform = near + (far - near) * f;
+            let log = near * (far / near).powf(f);
+            match *self {
+                SplitScheme::Uniform => uniform,
+                SplitScheme::Logarithmic => log,
+                SplitScheme::Practical(lambda) => log * lambda + uniform * (S::one() - lambda),
+            }
+        }).collect()
+    }
+}
+
+/// One slice of a split view frustum.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cascade<S: BaseFloat> {
+    /// The view distance of the cascade's near plane.
+    pub near: S,
+    /// The view distance of the cascade's far plane.
+    pub far: S,
+    /// The corners of the cascade.
+    pub points: FrustumPoints<S>,
+    /// The planes of the cascade.
+    pub frustum: Frustum<S>,
+}
+
+impl<S: BaseFloat + 'static> Cascade<S> {
+    /// Split a perspective frustum with the view distances `near` and `far`
+    /// into `count` cascades along its depth. Returns `None` unless `near`
+    /// is less than `far`, or if a cascade is degenerate.
+    pub fn split(points: &FrustumPoints<S>, near: S, far: S,
+                 count: usize, scheme: SplitScheme<S>) -> Option<Vec<Cascade<S>>> {
+        if near >= far || near.is_nan() || far.is_nan() {
+            return None;
+        }
+        let distances = scheme.distances(near, far, count);
+        distances.windows(2).map(|d| {
+            let slice = points.slice((d[0] - near) / (far - near), (d[1] - near) / (far - near));
+            slice.to_frustum().map(|frustum| Cascade {
+                near: d[0],
+                far: d[1],
+                points: slice,
+                frustum: frustum,
+            })
+        }).collect()
+    }
+
+    /// Fit an orthographic projection looking along `light_dir` around the
+    /// cascade, for a shadow map `resolution` texels across. The light view
+    /// matrix is anchored at the world origin and the projection bounds are
+    /// snapped to whole texels with a fixed size, so the shadow does not
+    /// shimmer as the camera moves. The projection's `near` plane may have
+    /// to be pulled back to include shadow casters outside of the cascade.
+    pub fn light_projection(&self, light_dir: Vector3<S>, resolution: S) -> (Matrix4<S>, Ortho<S>) {
+        let dir = light_dir.normalize();
+        let up = if dir.x.abs() < dir.y.abs() && dir.x.abs() < dir.z.abs() {
+            Vector3::unit_x()
+        } else if dir.y.abs() < dir.z.abs() {
+            Vector3::unit_y()
+        } else {
+            Vector3::unit_z()
+        };
+        let view = Matrix4::look_at(Point3::origin(), Point3::from_vec(dir), up);
+
+        let corners = self.points.to_corners();
+        let mut light = [Point3::origin(); 8];
+        for (l, c) in light.iter_mut().zip(corners.iter()) {
+            *l = Point3::from_homogeneous(view * c.to_homogeneous());
+        }
+        // pad the bounding square by a texel so snapping never clips it
+        let sphere = FrustumPoints::from_corners(light).to_sphere();
+        let texel = (sphere.radius + sphere.radius) / (resolution - S::one());
+        let size = texel * resolution;
+        let snap = |x: S| (x / texel).floor() * texel;
+
+        let left = snap(sphere.center.x - sphere.radius);
+        let bottom = snap(sphere.center.y - sphere.radius);
+        let (min_z, max_z) = light.iter().fold((S::infinity(), S::neg_infinity()),
+                                               |(lo, hi), p| (lo.min(p.z), hi.max(p.z)));
+
+        (view, Ortho {
+            left: left,
+            right: left + size,
+            bottom: bottom,
+            top: bottom + size,
+            near: -max_z,
+            far: -min_z,
+        })
+    }
+}
@@ -0,0 +1,304 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integer cells of tile maps and voxel regions
+//!
+//! An integer box covers the unit cells whose lower corners lie in the
+//! half-open range `[min, max)`, so boxes sharing a face share no cells.
+//! Float bounds and rays are mapped onto the unit grid, where the cell `c`
+//! spans `[c, c + 1]` along every axis.
+
+use std::marker::PhantomData;
+
+use cgmath::{Array, BaseFloat, BaseNum};
+use cgmath::{Point2, Point3};
+
+use {Aabb2, Aabb3, Ray2, Ray3};
+use aabb::axes;
+
+/// An iterator over the integer cells of a box, with the first axis varying
+/// fastest.
+pub struct Cells<P> {
+    min: P,
+    max: P,
+    next: Option<P>,
+}
+
+impl<S: BaseNum, P: Array<Element=S> + Copy> Cells<P> {
+    fn new(min: P, max: P) -> Cells<P> {
+        let empty = (0..axes::<S, P>()).any(|i| max[i] <= min[i]);
+        Cells {
+            min: min,
+            max: max,
+            next: if empty { None } else { Some(min) },
+        }
+    }
+
+    fn advance(&self, mut c: P) -> Option<P> {
+        for i in 0..axes::<S, P>() {
+            c[i] = c[i] + S::one();
+            if c[i] < self.max[i] {
+                return Some(c);
+            }
+            c[i] = self.min[i];
+        }
+        None
+    }
+}
+
+impl<S: BaseNum, P: Array<Element=S> + Copy> Iterator for Cells<P> {
+    type Item = P;
+
+    fn next(&mut self) -> Option<P> {
+        let cell = match self.next {
+            Some(c) => c,
+            None => return None,
+        };
+        self.next = self.advance(cell);
+        Some(cell)
+    }
+}
+
+impl<S: BaseNum> Aabb2<S> {
+    /// Iterate over the integer cells of the box.
+    #[inline]
+    pub fn cells(&self) -> Cells<Point2<S>> { Cells::new(self.min, self.max) }
+
+    /// Convert the box to another scalar type, or `None` if a coordinate
+    /// cannot be represented. Float coordinates are truncated towards zero.
+    pub fn cast<T: BaseNum>(&self) -> Option<Aabb2<T>> {
+        let id = |x: S| x;
+        match (convert(self.min, id), convert(self.max, id)) {
+            (Some(min), Some(max)) => Some(Aabb2 { min: min, max: max }),
+            _ => None,
+        }
+    }
+}
+
+impl<S: BaseNum> Aabb3<S> {
+    /// Iterate over the integer cells of the box.
+    #[inline]
+    pub fn cells(&self) -> Cells<Point3<S>> { Cells::new(self.min, self.max) }
+
+    /// Convert the box to another scalar type, or `None` if a coordinate
+    /// cannot be represented. Float coordinates are truncated towards zero.
+    pub fn cast<T: BaseNum>(&self) -> Option<Aabb3<T>> {
+        let id = |x: S| x;
+        match (convert(self.min, id), convert(self.max, id)) {
+            (Some(min), Some(max)) => Some(Aabb3 { min: min, max: max }),
+            _ => None,
+        }
+    }
+}
+
+impl<S: BaseFloat> Aabb2<S> {
+    /// The smallest integer box containing the box, rounding its minimum
+    /// down and its maximum up.
+    pub fn round_out<I: BaseNum>(&self) -> Option<Aabb2<I>> {
+        match (convert(self.min, S::floor), convert(self.max, S::ceil)) {
+            (Some(min), Some(max)) => Some(Aabb2 { min: min, max: max }),
+            _ => None,
+        }
+    }
+
+    /// The largest integer box inside the box, rounding its minimum up and
+    /// its maximum down. Axes without a whole cell collapse to the rounded
+    /// minimum, leaving the box without cells.
+    pub fn round_in<I: BaseNum>(&self) -> Option<Aabb2<I>> {
+        let (min, max) = round_in(self.min, self.max);
+        match (convert(min, S::ceil), convert(max, S::floor)) {
+            (Some(min), Some(max)) => Some(Aabb2 { min: min, max: max }),
+            _ => None,
+        }
+    }
+
+    /// The integer box of the cells the box touches, including the cells
+    /// only touched by its boundary.
+    pub fn cell_range<I: BaseNum>(&self) -> Option<Aabb2<I>> {
+        match (convert(self.min, S::floor), convert(self.max, |x: S| x.floor() + S::one())) {
+            (Some(min), Some(max)) => Some(Aabb2 { min: min, max: max }),
+            _ => None,
+        }
+    }
+}
+
+impl<S: BaseFloat> Aabb3<S> {
+    /// The smallest integer box containing the box, rounding its minimum
+    /// down and its maximum up.
+    pub fn round_out<I: BaseNum>(&self) -> Option<Aabb3<I>> {
+        match (convert(self.min, S::floor), convert(self.max, S::ceil)) {
+            (Some(min), Some(max)) => Some(Aabb3 { min: min, max: max }),
+            _ => None,
+        }
+    }
+
+    /// The largest integer box inside the box, rounding its minimum up and
+    /// its maximum down. Axes without a whole cell collapse to the rounded
+    /// minimum, leaving the box without cells.
+    pub fn round_in<I: BaseNum>(&self) -> Option<Aabb3<I>> {
+        let (min, max) = round_in(self.min, self.max);
+        match (convert(min, S::ceil), convert(max, S::floor)) {
+            (Some(min), Some(max)) => Some(Aabb3 { min: min, max: max }),
+            _ => None,
+        }
+    }
+
+    /// The integer box of the cells the box touches, including the cells
+    /// only touched by its boundary.
+    pub fn cell_range<I: BaseNum>(&self) -> Option<Aabb3<I>> {
+        match (convert(self.min, S::floor), convert(self.max, |x: S| x.floor() + S::one())) {
+            (Some(min), Some(max)) => Some(Aabb3 { min: min, max: max }),
+            _ => None,
+        }
+    }
+}
+
+impl<S: BaseFloat> Ray2<S> {
+    /// Iterate over the integer cells pierced by the ray up to the ray
+    /// parameter `max_t`, in order along the ray.
+    #[inline]
+    pub fn cells<I: BaseNum>(&self, max_t: S) -> RayCells<S, Point2<S>, Point2<I>> {
+        RayCells::new(self.origin, self.direction, S::one(), S::zero(), max_t)
+    }
+}
+
+impl<S: BaseFloat> Ray3<S> {
+    /// Iterate over the integer cells pierced by the ray up to the ray
+    /// parameter `max_t`, in order along the ray.
+    #[inline]
+    pub fn cells<I: BaseNum>(&self, max_t: S) -> RayCells<S, Point3<S>, Point3<I>> {
+        RayCells::new(self.origin, self.direction, S::one(), S::zero(), max_t)
+    }
+}
+
+/// An iterator over the cells of a grid pierced by a line, in order along
+/// the line, using the algorithm of Amanatides and Woo.
+///
+/// Where the line passes exactly through an edge or corner of a cell, only
+/// one of the cells meeting there is visited. Iteration stops at the first
+/// cell that cannot be represented.
+pub struct RayCells<S, P, Q> {
+    cell: P,
+    step: P,
+    t_next: P,
+    t_delta: P,
+    t_end: S,
+    done: bool,
+    phantom: PhantomData<Q>,
+}
+
+impl<S, P, Q> RayCells<S, P, Q>
+    where S: BaseFloat,
+          P: Array<Element=S> + Copy
+{
+    /// Traverse the cells of size `cell_size` pierced by the line
+    /// `origin + t * dir` for `t` from `t0` to `t1`. A line that does not
+    /// move visits a single cell; otherwise `t1` must be finite, or no cell
+    /// is visited.
+    pub fn new<V: Array<Element=S>>(origin: P, dir: V, cell_size: S, t0: S, t1: S) -> RayCells<S, P, Q> {
+        let mut cells = RayCells {
+            cell: P::from_value(S::zero()),
+            step: P::from_value(S::zero()),
+            t_next: P::from_value(S::infinity()),
+            t_delta: P::from_value(S::infinity()),
+            t_end: t1,
+            done: t0 > t1 || t0.is_nan() || t1.is_nan(),
+            phantom: PhantomData,
+        };
+        let still = (0..axes::<S, P>()).all(|i| dir[i] == S::zero());
+        if !still && !t1.is_finite() {
+            cells.done = true;
+        }
+        for i in 0..axes::<S, P>() {
+            let start = origin[i] + dir[i] * t0;
+            cells.cell[i] = (start / cell_size).floor();
+            let d = dir[i];
+            if d > S::zero() {
+                cells.step[i] = S::one();
+                cells.t_next[i] = t0 + ((cells.cell[i] + S::one()) * cell_size - start) / d;
+                cells.t_delta[i] = cell_size / d;
+            } else if d < S::zero() {
+                cells.step[i] = -S::one();
+                cells.t_next[i] = t0 + (cells.cell[i] * cell_size - start) / d;
+                cells.t_delta[i] = -cell_size / d;
+            }
+        }
+        cells
+    }
+}
+
+impl<S, I, P, Q> Iterator for RayCells<S, P, Q>
+    where S: BaseFloat,
+          I: BaseNum,
+          P: Array<Element=S> + Copy,
+          Q: Array<Element=I>
+{
+    type Item = Q;
+
+    fn next(&mut self) -> Option<Q> {
+        if self.done {
+            return None;
+        }
+        let cell = match convert(self.cell, |x: S| x) {
+            Some(c) => c,
+            None => {
+                self.done = true;
+                return None;
+            }
+        };
+        let mut axis = 0;
+        for i in 1..axes::<S, P>() {
+            if self.t_next[i] < self.t_next[axis] {
+                axis = i;
+            }
+        }
+        // a line that does not move never reaches the next cell
+        if self.t_next[axis] <= self.t_end && self.t_next[axis].is_finite() {
+            self.cell[axis] = self.cell[axis] + self.step[axis];
+            self.t_next[axis] = self.t_next[axis] + self.t_delta[axis];
+        } else {
+            self.done = true;
+        }
+        Some(cell)
+    }
+}
+
+fn convert<S, T, P, Q, F>(p: P, f: F) -> Option<Q>
+    where S: BaseNum,
+          T: BaseNum,
+          P: Array<Element=S>,
+          Q: Array<Element=T>,
+          F: Fn(S) -> S
+{
+    let mut q = Q::from_value(T::zero());
+    for i in 0..axes::<S, P>() {
+        q[i] = match T::from(f(p[i])) {
+            Some(x) => x,
+            None => return None,
+        };
+    }
+    Some(q)
+}
+
+/// Clamp the rounded maximum of a box to its rounded minimum, so rounding
+/// inwards never turns the box inside out.
+fn round_in<S: BaseFloat, P: Array<Element=S> + Copy>(min: P, mut max: P) -> (P, P) {
+    for i in 0..axes::<S, P>() {
+        if max[i].floor() < min[i].ceil() {
+            max[i] = min[i].ceil();
+        }
+    }
+    (min, max)
+}
@@ -0,0 +1,41 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bounding circle
+
+use bound::{Bound2, Relation};
+use HalfPlane;
+use cgmath::BaseFloat;
+use cgmath::Point2;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "rustc-serialize", derive(RustcEncodable, RustcDecodable))]
+pub struct Circle<S: BaseFloat> {
+    pub center: Point2<S>,
+    pub radius: S,
+}
+
+impl<S: BaseFloat + 'static> Bound2<S> for Circle<S> {
+    fn relate_half_plane(self, half_plane: HalfPlane<S>) -> Relation {
+        let dist = half_plane.distance(self.center);
+        if dist > self.radius {
+            Relation::In
+        } else if dist < -self.radius {
+            Relation::Out
+        } else {
+            Relation::Cross
+        }
+    }
+}
@@ -15,13 +15,106 @@
 
 //! Oriented bounding cylinder
 
+use cgmath::{BaseFloat, EuclideanSpace, InnerSpace, Zero};
 use cgmath::Point3;
 use cgmath::Vector3;
 
+use Ray3;
+use span::{RaySpan, Span};
+
 #[derive(Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "rustc-serialize", derive(RustcEncodable, RustcDecodable))]
 pub struct Cylinder<S> {
     pub center: Point3<S>,
+    /// The vector from the center to the center of one of the caps, half of
+    /// the cylinder's height.
     pub axis: Vector3<S>,
     pub radius: S,
 }
+
+impl<S: BaseFloat> Cylinder<S> {
+    /// Construct a new cylinder.
+    pub fn new(center: Point3<S>, axis: Vector3<S>, radius: S) -> Cylinder<S> {
+        Cylinder {
+            center: center,
+            axis: axis,
+            radius: radius,
+        }
+    }
+}
+
+impl<S: BaseFloat> RaySpan<S, Ray3<S>> for Cylinder<S> {
+    fn ray_span(&self, ray: &Ray3<S>) -> Option<Span<S>> {
+        clip_line(self, ray.origin, ray.direction, S::zero(), S::infinity()).map(|(t0, t1)| Span::new(t0, t1))
+    }
+}
+
+/// The point of a cylinder farthest along `dir`.
+pub fn support<S: BaseFloat>(cylinder: &Cylinder<S>, dir: Vector3<S>) -> Point3<S> {
+    let along = cylinder.axis.dot(dir);
+    let cap = if along < S::zero() { cylinder.center + (-cylinder.axis) } else { cylinder.center + cylinder.axis };
+    let radial = if cylinder.axis == Vector3::zero() {
+        dir
+    } else {
+        dir - cylinder.axis * (along / cylinder.axis.magnitude2())
+    };
+    if radial == Vector3::zero() {
+        cap
+    } else {
+        cap + radial.normalize() * cylinder.radius
+    }
+}
+
+/// Clip the line `origin + t * dir` to a cylinder, starting from the range
+/// `[t0, t1]`.
+pub fn clip_line<S: BaseFloat>(cylinder: &Cylinder<S>, origin: Point3<S>, dir: Vector3<S>,
+                               mut t0: S, mut t1: S) -> Option<(S, S)> {
+    let h2 = cylinder.axis.magnitude2();
+    let m = origin - cylinder.center;
+    let perp = |v: Vector3<S>| if h2 == S::zero() { v } else { v - cylinder.axis * (v.dot(cylinder.axis) / h2) };
+
+    // between the caps, `-h2 <= (m + t * dir) . axis <= h2`
+    let (z, dz) = (m.dot(cylinder.axis), dir.dot(cylinder.axis));
+    if dz == S::zero() {
+        if z.abs() > h2 {
+            return None;
+        }
+    } else {
+        let a = (-h2 - z) / dz;
+        let b = (h2 - z) / dz;
+        t0 = t0.max(a.min(b));
+        t1 = t1.min(a.max(b));
+    }
+
+    // inside the infinite cylinder, `|perp(m + t * dir)| <= radius`
+    let (mp, dp) = (perp(m), perp(dir));
+    let a = dp.dot(dp);
+    let b = mp.dot(dp);
+    let c = mp.dot(mp) - cylinder.radius * cylinder.radius;
+    if a == S::zero() {
+        if c > S::zero() {
+            return None;
+        }
+    } else {
+        let discr = b * b - a * c;
+        if discr < S::zero() {
+            return None;
+        }
+        let root = discr.sqrt();
+        t0 = t0.max((-b - root) / a);
+        t1 = t1.min((-b + root) / a);
+    }
+    if t0 <= t1 { Some((t0, t1)) } else { None }
+}
+
+/// The point of a cylinder nearest to `p`.
+pub fn closest_point<S: BaseFloat>(cylinder: &Cylinder<S>, p: Point3<S>) -> Point3<S> {
+    let h2 = cylinder.axis.magnitude2();
+    let m = p - cylinder.center;
+    let z = if h2 == S::zero() { S::zero() } else { (m.dot(cylinder.axis) / h2).max(-S::one()).min(S::one()) };
+    let along = cylinder.axis * z;
+    let radial = if h2 == S::zero() { m } else { m - cylinder.axis * (m.dot(cylinder.axis) / h2) };
+    let r = radial.magnitude();
+    let radial = if r > cylinder.radius { radial * (cylinder.radius / r) } else { radial };
+    Point3::from_vec(cylinder.center.to_vec() + along + radial)
+}
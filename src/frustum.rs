@@ -15,11 +15,16 @@
 
 //! View frustum for visibility determination
 
-use Plane;
+use {Aabb, Aabb3, Line3, Obb3, Plane, Ray3, Sphere};
 use bound::*;
-use cgmath::{Matrix, Matrix4};
+use intersect::Intersect;
+use plane;
+use span::{RaySpan, Span};
+use cgmath::{Matrix, Matrix4, SquareMatrix};
 use cgmath::BaseFloat;
-use cgmath::Point3;
+use cgmath::{EuclideanSpace, InnerSpace, MetricSpace};
+use cgmath::{Point2, Point3, Vector3, Vector4, Zero};
+use cgmath::{Angle, Quaternion, Rad};
 use cgmath::{PerspectiveFov, Ortho, Perspective};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -30,7 +35,33 @@ pub struct Frustum<S: BaseFloat> {
     pub bottom: Plane<S>,
     pub top:    Plane<S>,
     pub near:   Plane<S>,
-    pub far:    Plane<S>,
+    /// The far plane, or `None` if the frustum extends to infinity.
+    pub far:    Option<Plane<S>>,
+}
+
+/// The range of depths a projection matrix maps the view volume to, in
+/// normalized device coordinates.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum DepthRange {
+    /// The OpenGL convention, from `-1` at the near plane to `1` at the far
+    /// plane.
+    NegativeOneToOne,
+    /// The Direct3D and Vulkan convention, from `0` at the near plane to `1`
+    /// at the far plane.
+    ZeroToOne,
+    /// Reversed-Z, from `1` at the near plane to `0` at the far plane.
+    ReversedZeroToOne,
+}
+
+impl DepthRange {
+    /// The normalized device depths of the near and far planes.
+    pub fn near_far<S: BaseFloat>(&self) -> (S, S) {
+        match *self {
+            DepthRange::NegativeOneToOne => (-S::one(), S::one()),
+            DepthRange::ZeroToOne => (S::zero(), S::one()),
+            DepthRange::ReversedZeroToOne => (S::one(), S::zero()),
+        }
+    }
 }
 
 impl<S: BaseFloat + 'static> Frustum<S> {
@@ -44,32 +75,148 @@ impl<S: BaseFloat + 'static> Frustum<S> {
             bottom: bottom,
             top:    top,
             near:   near,
-            far:    far,
+            far:    Some(far),
+        }
+    }
+
+    /// Construct a frustum without a far plane.
+    pub fn new_infinite(left:   Plane<S>, right:  Plane<S>,
+                        bottom: Plane<S>, top:    Plane<S>,
+                        near:   Plane<S>) -> Frustum<S> {
+        Frustum {
+            left:   left,
+            right:  right,
+            bottom: bottom,
+            top:    top,
+            near:   near,
+            far:    None,
         }
     }
 
-    /// Extract frustum planes from a projection matrix.
+    /// Extract frustum planes from a projection matrix, assuming the OpenGL
+    /// depth range.
     pub fn from_matrix4(mat: Matrix4<S>) -> Option<Frustum<S>> {
-        Some(Frustum::new(
-            match Plane::from_vector4_alt(mat.row(3) + mat.row(0)).normalize()
+        Frustum::from_matrix4_depth(mat, DepthRange::NegativeOneToOne)
+    }
+
+    /// Extract frustum planes from a projection matrix with the given depth
+    /// range. The far plane of an infinite projection is left out. Returns
+    /// `None` if a plane is degenerate or the matrix is not finite.
+    pub fn from_matrix4_depth(mat: Matrix4<S>, depth: DepthRange) -> Option<Frustum<S>> {
+        let finite = |v: Vector4<S>| v.x.is_finite() && v.y.is_finite() && v.z.is_finite() && v.w.is_finite();
+        if !(0..4).all(|i| finite(mat.row(i))) {
+            return None;
+        }
+        let (near, far) = match depth {
+            DepthRange::NegativeOneToOne => (mat.row(3) + mat.row(2), mat.row(3) - mat.row(2)),
+            DepthRange::ZeroToOne => (mat.row(2), mat.row(3) - mat.row(2)),
+            DepthRange::ReversedZeroToOne => (mat.row(3) - mat.row(2), mat.row(2)),
+        };
+        let far = Plane::from_vector4_alt(far);
+        Some(Frustum {
+            left: match Plane::from_vector4_alt(mat.row(3) + mat.row(0)).normalize()
                 { Some(p) => p, None => return None },
-            match Plane::from_vector4_alt(mat.row(3) - mat.row(0)).normalize()
+            right: match Plane::from_vector4_alt(mat.row(3) - mat.row(0)).normalize()
                 { Some(p) => p, None => return None },
-            match Plane::from_vector4_alt(mat.row(3) + mat.row(1)).normalize()
+            bottom: match Plane::from_vector4_alt(mat.row(3) + mat.row(1)).normalize()
                 { Some(p) => p, None => return None },
-            match Plane::from_vector4_alt(mat.row(3) - mat.row(1)).normalize()
+            top: match Plane::from_vector4_alt(mat.row(3) - mat.row(1)).normalize()
                 { Some(p) => p, None => return None },
-            match Plane::from_vector4_alt(mat.row(3) + mat.row(2)).normalize()
+            near: match Plane::from_vector4_alt(near).normalize()
                 { Some(p) => p, None => return None },
-            match Plane::from_vector4_alt(mat.row(3) - mat.row(2)).normalize()
-                { Some(p) => p, None => return None }
-        ))
+            // the far plane of an infinite projection has no normal, and
+            // does not cut off anything in front of the camera
+            far: far.normalize(),
+        })
+    }
+
+    /// Whether the frustum has no far plane.
+    #[inline]
+    pub fn is_infinite(&self) -> bool { self.far.is_none() }
+
+    /// Construct the world space frustum of a perspective camera at `eye`,
+    /// rotated by `orientation` from looking down the negative z axis.
+    /// Returns `None` if the field of view is not between zero and a half
+    /// turn, the aspect ratio is not positive, the near distance is not
+    /// positive and less than the far distance, or if an orientation that
+    /// is not a unit quaternion collapses a plane.
+    pub fn from_camera(eye: Point3<S>, orientation: Quaternion<S>,
+                       fovy: Rad<S>, aspect: S, near: S, far: S) -> Option<Frustum<S>> {
+        let valid = fovy > Rad::zero() && fovy < Rad::turn_div_2() &&
+                    aspect > S::zero() && aspect.is_finite() &&
+                    near > S::zero() && near < far;
+        if !valid {
+            return None;
+        }
+        let view = Matrix4::from(orientation.conjugate()) * Matrix4::from_translation(-eye.to_vec());
+        let proj = PerspectiveFov { fovy: fovy, aspect: aspect, near: near, far: far };
+        proj.to_frustum().to_world(view)
+    }
+
+    /// Extract the frustum of a rectangle on the screen from a projection
+    /// matrix, for picking or portals. The rectangle is given in normalized
+    /// device coordinates, from `-1` to `1` with `y` pointing up. Returns
+    /// `None` if the rectangle has no area.
+    pub fn from_matrix4_rect(mat: Matrix4<S>, depth: DepthRange,
+                             min: Point2<S>, max: Point2<S>) -> Option<Frustum<S>> {
+        let (w, h) = (max.x - min.x, max.y - min.y);
+        if w == S::zero() || h == S::zero() || !w.is_finite() || !h.is_finite() {
+            return None;
+        }
+        let two = S::one() + S::one();
+        let (zero, one) = (S::zero(), S::one());
+        let rect = Matrix4::new(
+            two / w, zero, zero, zero,
+            zero, two / h, zero, zero,
+            zero, zero, one, zero,
+            -(max.x + min.x) / w, -(max.y + min.y) / h, zero, one,
+        );
+        Frustum::from_matrix4_depth(rect * mat, depth)
+    }
+
+    /// Transform a frustum from view space into world space, given the view
+    /// matrix taking world space to view space. Returns `None` if the view
+    /// matrix collapses a plane.
+    pub fn to_world(&self, view: Matrix4<S>) -> Option<Frustum<S>> {
+        let transposed = view.transpose();
+        let transform = |p: Plane<S>| {
+            Plane::from_vector4_alt(transposed * p.to_vector4_alt()).normalize()
+        };
+        Some(Frustum {
+            left: match transform(self.left) { Some(p) => p, None => return None },
+            right: match transform(self.right) { Some(p) => p, None => return None },
+            bottom: match transform(self.bottom) { Some(p) => p, None => return None },
+            top: match transform(self.top) { Some(p) => p, None => return None },
+            near: match transform(self.near) { Some(p) => p, None => return None },
+            far: match self.far {
+                Some(far) => match transform(far) { Some(p) => Some(p), None => return None },
+                None => None,
+            },
+        })
+    }
+
+    /// Compute the corners of the frustum by intersecting its planes.
+    /// Returns `None` if three of the planes do not meet in a point, or if
+    /// the frustum is infinite.
+    pub fn to_points(&self) -> Option<FrustumPoints<S>> {
+        let far = match self.far { Some(far) => far, None => return None };
+        let planes = [
+            (self.near, self.top, self.left), (self.near, self.top, self.right),
+            (self.near, self.bottom, self.left), (self.near, self.bottom, self.right),
+            (far, self.top, self.left), (far, self.top, self.right),
+            (far, self.bottom, self.left), (far, self.bottom, self.right),
+        ];
+        let mut corners = [Point3::origin(); 8];
+        for (corner, p) in corners.iter_mut().zip(planes.iter()) {
+            *corner = match p.intersection() { Some(c) => c, None => return None };
+        }
+        Some(FrustumPoints::from_corners(corners))
     }
 
     /// Find the spatial relation of a bound inside this frustum.
     pub fn contains<B: Bound<S>+Copy>(&self, bound: B) -> Relation {
-        [self.left, self.right, self.top, self.bottom, self.near, self.far]
-            .iter().fold(Relation::In, |cur, p| {
+        let (planes, count) = self.plane_array();
+        planes[..count].iter().fold(Relation::In, |cur, p| {
             use std::cmp::max;
             let r = bound.relate_plane(*p);
             // If any of the planes are `Out`, the bound is outside.
@@ -78,9 +225,176 @@ impl<S: BaseFloat + 'static> Frustum<S> {
             max(cur, r)
         })
     }
+
+    /// Return the planes in the order `left`, `right`, `bottom`, `top`,
+    /// `near`, `far`, leaving out the far plane of an infinite frustum. Bit
+    /// `i` of a `PlaneMask` refers to plane `i` here.
+    pub fn planes(&self) -> Vec<Plane<S>> {
+        let (planes, count) = self.plane_array();
+        planes[..count].to_vec()
+    }
+
+    /// The planes as by `planes`, and how many of them there are.
+    fn plane_array(&self) -> ([Plane<S>; 6], usize) {
+        match self.far {
+            Some(far) => ([self.left, self.right, self.bottom, self.top, self.near, far], 6),
+            None => ([self.left, self.right, self.bottom, self.top, self.near, self.near], 5),
+        }
+    }
+
+    /// Find the spatial relation of a bound inside this frustum, testing only
+    /// the planes in `mask`. Also returns the planes the bound crosses,
+    /// which is the mask to use for bounds nested inside of this one.
+    ///
+    /// `last_out` is the plane that last rejected the bound. It is tested
+    /// first, and updated if another plane rejects the bound, exploiting
+    /// temporal coherence between frames. Start it at `0` for new bounds.
+    ///
+    /// See _Optimized View Frustum Culling Algorithms for Bounding Boxes_,
+    /// Assarsson and Möller, 2000.
+    pub fn contains_masked<B: Bound<S>>(&self, bound: B, mask: PlaneMask,
+                                        last_out: &mut usize) -> (Relation, PlaneMask) {
+        let (planes, count) = self.plane_array();
+        let start = *last_out % count;
+        let mut crossed = PlaneMask::none();
+        for k in 0..count {
+            let i = (start + k) % count;
+            if !mask.has(i) {
+                continue;
+            }
+            match bound.relate_plane(planes[i]) {
+                Relation::Out => {
+                    *last_out = i;
+                    return (Relation::Out, mask);
+                }
+                Relation::Cross => crossed = crossed.with(i),
+                Relation::In => {}
+            }
+        }
+        if crossed.is_empty() { (Relation::In, crossed) } else { (Relation::Cross, crossed) }
+    }
 }
 
-#[derive(Copy, Clone, PartialEq)]
+impl<S: BaseFloat + 'static> Frustum<S> {
+    /// Test whether a box overlaps the frustum, using the separating axis
+    /// theorem. Unlike `contains`, this does not report boxes near the edges
+    /// of the frustum as overlapping when they are not. If the corners of
+    /// the frustum cannot be computed, only the planes are tested.
+    pub fn overlaps_aabb(&self, aabb: &Aabb3<S>) -> bool {
+        let axes = [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()];
+        self.overlaps_hull(&aabb.to_corners(), &axes, &axes)
+    }
+
+    /// Test whether an oriented box overlaps the frustum, using the
+    /// separating axis theorem.
+    pub fn overlaps_obb(&self, obb: &Obb3<S>) -> bool {
+        let axes = obb.axes();
+        self.overlaps_hull(&obb.to_corners(), &axes, &axes)
+    }
+
+    /// Test whether two frusta overlap, using the separating axis theorem.
+    pub fn overlaps_frustum(&self, other: &Frustum<S>) -> bool {
+        let normals = |f: &Frustum<S>| f.planes().iter().map(|p| p.n).collect::<Vec<_>>();
+        match (self.to_points(), other.to_points()) {
+            (_, Some(points)) => self.overlaps_hull(&points.to_corners(), &normals(other), &points.edges()),
+            (Some(points), None) => other.overlaps_hull(&points.to_corners(), &normals(self), &points.edges()),
+            (None, None) => true,
+        }
+    }
+
+    /// Clip a ray against the frustum, returning the ray parameters at which
+    /// it enters and leaves the frustum. The entry is `0` if the ray starts
+    /// inside.
+    pub fn clip_ray(&self, ray: &Ray3<S>) -> Option<(S, S)> {
+        self.clip(ray.origin, ray.direction, S::zero(), S::infinity())
+    }
+
+    /// Clip a line segment against the frustum, returning the parameters of
+    /// the part inside, from `0` at the origin to `1` at the destination.
+    pub fn clip_line(&self, line: &Line3<S>) -> Option<(S, S)> {
+        self.clip(line.origin, line.dest - line.origin, S::zero(), S::one())
+    }
+
+    fn clip(&self, origin: Point3<S>, dir: Vector3<S>, t0: S, t1: S) -> Option<(S, S)> {
+        let (planes, count) = self.plane_array();
+        plane::clip_line(&planes[..count], origin, dir, t0, t1)
+    }
+
+    /// Test the frustum against a convex hull given by its corners, face
+    /// normals and edge directions.
+    fn overlaps_hull(&self, corners: &[Point3<S>], normals: &[Vector3<S>],
+                     edges: &[Vector3<S>]) -> bool {
+        let (planes, count) = self.plane_array();
+        for p in planes[..count].iter() {
+            if corners.iter().all(|c| p.distance(*c) < S::zero()) {
+                return false;
+            }
+        }
+        let points = match self.to_points() { Some(p) => p, None => return true };
+        let own = points.to_corners();
+        if normals.iter().any(|n| separated(&own, corners, *n)) {
+            return false;
+        }
+        for e in points.edges().iter() {
+            if edges.iter().any(|f| separated(&own, corners, e.cross(*f))) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl<S: BaseFloat + 'static> RaySpan<S, Ray3<S>> for Frustum<S> {
+    #[inline]
+    fn ray_span(&self, ray: &Ray3<S>) -> Option<Span<S>> {
+        self.clip_ray(ray).map(|(t0, t1)| Span::new(t0, t1))
+    }
+}
+
+/// Whether the projections of two point sets on `axis` are disjoint. Axes
+/// from the cross product of nearly parallel edges are ignored.
+fn separated<S: BaseFloat>(a: &[Point3<S>], b: &[Point3<S>], axis: Vector3<S>) -> bool {
+    if axis.magnitude2() < S::epsilon() {
+        return false;
+    }
+    let project = |points: &[Point3<S>]| points.iter().fold((S::infinity(), S::neg_infinity()), |(lo, hi), p| {
+        let d = axis.dot(p.to_vec());
+        (lo.min(d), hi.max(d))
+    });
+    let (a_min, a_max) = project(a);
+    let (b_min, b_max) = project(b);
+    a_max < b_min || b_max < a_min
+}
+
+/// A set of frustum planes, one bit per plane in the order of
+/// `Frustum::planes`.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PlaneMask(pub u8);
+
+impl PlaneMask {
+    /// All six planes.
+    #[inline]
+    pub fn all() -> PlaneMask { PlaneMask(0b11_1111) }
+
+    /// No planes.
+    #[inline]
+    pub fn none() -> PlaneMask { PlaneMask(0) }
+
+    /// Whether the mask contains no planes.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.0 == 0 }
+
+    /// Whether the mask contains plane `i`.
+    #[inline]
+    pub fn has(&self, i: usize) -> bool { self.0 & (1 << i) != 0 }
+
+    /// Return the mask with plane `i` added.
+    #[inline]
+    pub fn with(&self, i: usize) -> PlaneMask { PlaneMask(self.0 | (1 << i)) }
+}
+
+/// The eight corners of a view frustum.
+#[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "rustc-serialize", derive(RustcEncodable, RustcDecodable))]
 pub struct FrustumPoints<S> {
     pub near_top_left:     Point3<S>,
@@ -93,34 +407,233 @@ pub struct FrustumPoints<S> {
     pub far_bottom_right:  Point3<S>,
 }
 
+impl<S: BaseFloat> FrustumPoints<S> {
+    /// Compute the corners of the frustum of a projection matrix by
+    /// unprojecting the corners of the OpenGL clip volume. Returns `None` if
+    /// the matrix is not invertible.
+    pub fn from_matrix4(mat: Matrix4<S>) -> Option<FrustumPoints<S>> {
+        FrustumPoints::from_matrix4_depth(mat, DepthRange::NegativeOneToOne)
+    }
+
+    /// Compute the corners of the frustum of a projection matrix with the
+    /// given depth range. Returns `None` if the matrix is not invertible, or
+    /// if the projection has an infinite far plane.
+    pub fn from_matrix4_depth(mat: Matrix4<S>, depth: DepthRange) -> Option<FrustumPoints<S>> {
+        let inv = match mat.invert() { Some(m) => m, None => return None };
+        let one = S::one();
+        let (n, f) = depth.near_far();
+        let ndc = [
+            (-one,  one, n), ( one,  one, n), (-one, -one, n), ( one, -one, n),
+            (-one,  one, f), ( one,  one, f), (-one, -one, f), ( one, -one, f),
+        ];
+        let mut corners = [Point3::origin(); 8];
+        for (corner, &(x, y, z)) in corners.iter_mut().zip(ndc.iter()) {
+            let p = inv * Vector4::new(x, y, z, one);
+            if ulps_eq!(p.w, &S::zero()) {
+                return None;
+            }
+            *corner = Point3::from_homogeneous(p);
+            if !corner.x.is_finite() || !corner.y.is_finite() || !corner.z.is_finite() {
+                return None;
+            }
+        }
+        Some(FrustumPoints::from_corners(corners))
+    }
+
+    /// Construct from an array of corners, ordered as by `to_corners`.
+    pub fn from_corners(corners: [Point3<S>; 8]) -> FrustumPoints<S> {
+        FrustumPoints {
+            near_top_left:     corners[0],
+            near_top_right:    corners[1],
+            near_bottom_left:  corners[2],
+            near_bottom_right: corners[3],
+            far_top_left:      corners[4],
+            far_top_right:     corners[5],
+            far_bottom_left:   corners[6],
+            far_bottom_right:  corners[7],
+        }
+    }
+
+    /// Return the corners as an array, near corners first, each plane
+    /// ordered top left, top right, bottom left, bottom right.
+    pub fn to_corners(&self) -> [Point3<S>; 8] {
+        [self.near_top_left, self.near_top_right,
+         self.near_bottom_left, self.near_bottom_right,
+         self.far_top_left, self.far_top_right,
+         self.far_bottom_left, self.far_bottom_right]
+    }
+
+    /// Cut out the part of the frustum between the fractions `t0` and `t1`
+    /// of its depth, interpolating along the edges from the near to the far
+    /// corners.
+    pub fn slice(&self, t0: S, t1: S) -> FrustumPoints<S> {
+        let lerp = |a: Point3<S>, b: Point3<S>, t: S| a + (b - a) * t;
+        let c = self.to_corners();
+        let mut corners = c;
+        for i in 0..4 {
+            corners[i] = lerp(c[i], c[i + 4], t0);
+            corners[i + 4] = lerp(c[i], c[i + 4], t1);
+        }
+        FrustumPoints::from_corners(corners)
+    }
+
+    /// Compute the planes of the frustum, with normals facing inwards.
+    /// Returns `None` if any face of the frustum is degenerate or not
+    /// finite.
+    pub fn to_frustum(&self) -> Option<Frustum<S>> {
+        let center = Point3::centroid(&self.to_corners());
+        let plane = |a: Point3<S>, b: Point3<S>, c: Point3<S>| {
+            let n = (b - a).cross(c - a);
+            if ulps_eq!(n, &Vector3::zero()) || !n.magnitude2().is_finite() {
+                return None;
+            }
+            let n = n.normalize();
+            let n = if n.dot(center - a) < S::zero() { -n } else { n };
+            Some(Plane::from_point_normal(a, n))
+        };
+        let faces = [
+            (self.near_top_left, self.near_bottom_left, self.far_bottom_left),
+            (self.near_top_right, self.far_top_right, self.far_bottom_right),
+            (self.near_bottom_left, self.near_bottom_right, self.far_bottom_right),
+            (self.near_top_left, self.far_top_left, self.far_top_right),
+            (self.near_top_left, self.near_top_right, self.near_bottom_right),
+            (self.far_top_left, self.far_bottom_left, self.far_bottom_right),
+        ];
+        let mut planes = [Plane::new(Vector3::zero(), S::zero()); 6];
+        for (p, &(a, b, c)) in planes.iter_mut().zip(faces.iter()) {
+            *p = match plane(a, b, c) { Some(p) => p, None => return None };
+        }
+        Some(Frustum {
+            left: planes[0],
+            right: planes[1],
+            bottom: planes[2],
+            top: planes[3],
+            near: planes[4],
+            far: Some(planes[5]),
+        })
+    }
+
+    /// The unit directions of the twelve edges of the frustum.
+    fn edges(&self) -> [Vector3<S>; 12] {
+        let c = self.to_corners();
+        let pairs = [(0, 1), (2, 3), (0, 2), (1, 3), (4, 5), (6, 7),
+                     (4, 6), (5, 7), (0, 4), (1, 5), (2, 6), (3, 7)];
+        let mut edges = [Vector3::zero(); 12];
+        for (e, &(a, b)) in edges.iter_mut().zip(pairs.iter()) {
+            let d = c[b] - c[a];
+            if d != Vector3::zero() {
+                *e = d.normalize();
+            }
+        }
+        edges
+    }
+
+    /// Compute the smallest axis-aligned box enclosing the corners.
+    pub fn to_aabb(&self) -> Aabb3<S> {
+        let corners = self.to_corners();
+        corners[1..].iter().fold(Aabb3::new(corners[0], corners[0]), |aabb, p| aabb.grow(*p))
+    }
+
+    /// Compute a sphere enclosing the corners, centered on their centroid.
+    pub fn to_sphere(&self) -> Sphere<S> {
+        let corners = self.to_corners();
+        let center = Point3::centroid(&corners);
+        let radius = corners.iter().fold(S::zero(), |r, p| r.max(center.distance(*p)));
+        Sphere { center: center, radius: radius }
+    }
+}
+
+/// Compute the view space corners of an off-center perspective frustum,
+/// looking down the negative z axis.
+fn perspective_points<S: BaseFloat>(left: S, right: S, bottom: S, top: S,
+                                    near: S, far: S) -> Option<FrustumPoints<S>> {
+    if !far.is_finite() {
+        return None;
+    }
+    let scale = far / near;
+    Some(FrustumPoints {
+        near_top_left:     Point3::new(left, top, -near),
+        near_top_right:    Point3::new(right, top, -near),
+        near_bottom_left:  Point3::new(left, bottom, -near),
+        near_bottom_right: Point3::new(right, bottom, -near),
+        far_top_left:      Point3::new(left * scale, top * scale, -far),
+        far_top_right:     Point3::new(right * scale, top * scale, -far),
+        far_bottom_left:   Point3::new(left * scale, bottom * scale, -far),
+        far_bottom_right:  Point3::new(right * scale, bottom * scale, -far),
+    })
+}
+
+fn without_far<S: BaseFloat + 'static>(f: Frustum<S>) -> Frustum<S> {
+    Frustum::new_infinite(f.left, f.right, f.bottom, f.top, f.near)
+}
 
 pub trait Projection<S: BaseFloat>: Into<Matrix4<S>> {
     fn to_frustum(&self) -> Frustum<S>;
+
+    /// Compute the view space corners of the projection's frustum, or
+    /// `None` if it has an infinite far plane. By default the corners are
+    /// found by inverting the projection matrix.
+    fn to_points(&self) -> Option<FrustumPoints<S>> where Self: Clone {
+        FrustumPoints::from_matrix4(self.clone().into())
+    }
 }
 
 impl<S: BaseFloat + 'static> Projection<S> for PerspectiveFov<S> {
     fn to_frustum(&self) -> Frustum<S> {
+        if !self.far.is_finite() {
+            // the side and near planes do not depend on the far distance
+            let finite = PerspectiveFov { far: self.near + self.near, ..*self };
+            return without_far(finite.to_frustum());
+        }
         // TODO: Could this be faster?
         Frustum::from_matrix4(self.clone().into()).unwrap()
     }
+
+    fn to_points(&self) -> Option<FrustumPoints<S>> {
+        self.to_perspective().to_points()
+    }
 }
 
 impl<S: BaseFloat + 'static> Projection<S> for Perspective<S> {
     fn to_frustum(&self) -> Frustum<S> {
+        if !self.far.is_finite() {
+            let finite = Perspective { far: self.near + self.near, ..*self };
+            return without_far(finite.to_frustum());
+        }
         // TODO: Could this be faster?
         Frustum::from_matrix4(self.clone().into()).unwrap()
     }
+
+    fn to_points(&self) -> Option<FrustumPoints<S>> {
+        perspective_points(self.left, self.right, self.bottom, self.top, self.near, self.far)
+    }
 }
 
 impl<S: BaseFloat> Projection<S> for Ortho<S> {
     fn to_frustum(&self) -> Frustum<S> {
         Frustum {
             left:   Plane::from_abcd(S::one(), S::zero(), S::zero(), self.left.clone()),
-            right:  Plane::from_abcd(-S::one(), S::zero(), S::zero(), self.right.clone()),
+            right:  Plane::from_abcd(-S::one(), S::zero(), S::zero(), -self.right),
             bottom: Plane::from_abcd(S::zero(), S::one(), S::zero(), self.bottom.clone()),
-            top:    Plane::from_abcd(S::zero(), -S::one(), S::zero(), self.top.clone()),
+            top:    Plane::from_abcd(S::zero(), -S::one(), S::zero(), -self.top),
             near:   Plane::from_abcd(S::zero(), S::zero(), -S::one(), self.near.clone()),
-            far:    Plane::from_abcd(S::zero(), S::zero(), S::one(), self.far.clone()),
+            far:    Some(Plane::from_abcd(S::zero(), S::zero(), S::one(), -self.far)),
         }
     }
+
+    fn to_points(&self) -> Option<FrustumPoints<S>> {
+        if !self.far.is_finite() {
+            return None;
+        }
+        Some(FrustumPoints {
+            near_top_left:     Point3::new(self.left, self.top, -self.near),
+            near_top_right:    Point3::new(self.right, self.top, -self.near),
+            near_bottom_left:  Point3::new(self.left, self.bottom, -self.near),
+            near_bottom_right: Point3::new(self.right, self.bottom, -self.near),
+            far_top_left:      Point3::new(self.left, self.top, -self.far),
+            far_top_right:     Point3::new(self.right, self.top, -self.far),
+            far_bottom_left:   Point3::new(self.left, self.bottom, -self.far),
+            far_bottom_right:  Point3::new(self.right, self.bottom, -self.far),
+        })
+    }
 }
@@ -0,0 +1,77 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Two-dimensional view regions
+
+use bound::{Bound2, Relation};
+use HalfPlane;
+use cgmath::{BaseFloat, Matrix2, Rad};
+use cgmath::{Ortho, Point2, Vector2};
+
+/// A two-dimensional view region, bounded by four half-planes. The region
+/// seen by a 2D camera is a rectangle, which may be rotated.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "rustc-serialize", derive(RustcEncodable, RustcDecodable))]
+pub struct Frustum2<S: BaseFloat> {
+    pub left: HalfPlane<S>,
+    pub right: HalfPlane<S>,
+    pub bottom: HalfPlane<S>,
+    pub top: HalfPlane<S>,
+}
+
+impl<S: BaseFloat + 'static> Frustum2<S> {
+    /// Construct a view region.
+    pub fn new(left: HalfPlane<S>, right: HalfPlane<S>,
+               bottom: HalfPlane<S>, top: HalfPlane<S>) -> Frustum2<S> {
+        Frustum2 {
+            left: left,
+            right: right,
+            bottom: bottom,
+            top: top,
+        }
+    }
+
+    /// The region seen through an orthographic projection, in view space.
+    /// The depth range of the projection is ignored.
+    pub fn from_ortho(ortho: &Ortho<S>) -> Frustum2<S> {
+        let (x, y) = (Vector2::unit_x(), Vector2::unit_y());
+        Frustum2::new(HalfPlane::new(x, ortho.left), HalfPlane::new(-x, -ortho.right),
+                      HalfPlane::new(y, ortho.bottom), HalfPlane::new(-y, -ortho.top))
+    }
+
+    /// The region seen by a camera at `center`, rotated counter-clockwise by
+    /// `rotation` and seeing `extents` units to either side.
+    pub fn from_camera(center: Point2<S>, rotation: Rad<S>, extents: Vector2<S>) -> Frustum2<S> {
+        let m = Matrix2::from_angle(rotation);
+        let (x, y) = (m.x, m.y);
+        Frustum2::new(HalfPlane::from_point_normal(center + x * -extents.x, x),
+                      HalfPlane::from_point_normal(center + x * extents.x, -x),
+                      HalfPlane::from_point_normal(center + y * -extents.y, y),
+                      HalfPlane::from_point_normal(center + y * extents.y, -y))
+    }
+
+    /// The half-planes in the order `left`, `right`, `bottom`, `top`.
+    pub fn half_planes(&self) -> [HalfPlane<S>; 4] {
+        [self.left, self.right, self.bottom, self.top]
+    }
+
+    /// Find the spatial relation of a bound inside this region.
+    pub fn contains<B: Bound2<S>>(&self, bound: B) -> Relation {
+        self.half_planes().iter().fold(Relation::In, |cur, h| {
+            use std::cmp::max;
+            max(cur, bound.relate_half_plane(*h))
+        })
+    }
+}
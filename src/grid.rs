@@ -0,0 +1,398 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Uniform grids and spatial hashes
+//!
+//! A grid partitions space into cubic cells of a fixed size and stores every
+//! handle in each cell its bound overlaps. It is a cheap broad phase for many
+//! similarly sized objects, such as particles or crowds, where a hierarchy
+//! would be overkill.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::{i32, u64};
+
+use cgmath::{BaseFloat, EuclideanSpace, VectorSpace, Array, ElementWise};
+use cgmath::{Point2, Point3, Vector2, Vector3};
+
+use {Aabb, Aabb2, Aabb3, Boundary, Ray, RayCells, Sphere};
+use aabb::axes;
+
+/// A box that can be stored in a grid or a tree, naming the types of its
+/// space.
+pub trait GridBound<S: BaseFloat>: Copy {
+    /// The point type of the bound's space.
+    type Point: EuclideanSpace<Scalar=S, Diff=Self::Vector>;
+    /// The vector type of the bound's space.
+    type Vector: VectorSpace<Scalar=S> + ElementWise + Array<Element=S>;
+    /// Integer coordinates of a grid cell.
+    type Cell: Copy + Eq + Hash + Array<Element=i32>;
+}
+
+impl<S: BaseFloat> GridBound<S> for Aabb2<S> {
+    type Point = Point2<S>;
+    type Vector = Vector2<S>;
+    type Cell = Point2<i32>;
+}
+
+impl<S: BaseFloat> GridBound<S> for Aabb3<S> {
+    type Point = Point3<S>;
+    type Vector = Vector3<S>;
+    type Cell = Point3<i32>;
+}
+
+/// The most cells a grid or a stored bound may cover.
+const MAX_CELLS: u64 = 1 << 24;
+
+enum Storage<C, T> {
+    Hashed(HashMap<C, Vec<T>>),
+    Uniform { lo: C, hi: C, cells: Vec<Vec<T>> },
+}
+
+/// A grid of cells storing handles by their bounds.
+///
+/// The grid is either hashed, covering all of space with cells allocated on
+/// demand, or uniform, covering a fixed region with a dense array of cells.
+/// Bounds reaching outside of a uniform grid are stored in its border cells.
+///
+/// Bounds that are not finite, or that cover more than 2^24 cells, are not
+/// stored.
+pub struct Grid<S: BaseFloat, B: GridBound<S>, T> {
+    cell_size: S,
+    storage: Storage<B::Cell, T>,
+    entries: HashMap<T, B>,
+    extent: Option<B>,
+}
+
+/// A grid of two-dimensional boxes.
+pub type Grid2<S, T> = Grid<S, Aabb2<S>, T>;
+/// A grid of three-dimensional boxes.
+pub type Grid3<S, T> = Grid<S, Aabb3<S>, T>;
+
+impl<S, B, T> Grid<S, B, T>
+    where S: BaseFloat,
+          B: GridBound<S> + Aabb<S, B::Vector, B::Point>,
+          T: Copy + Eq + Hash
+{
+    /// Create an unbounded grid, hashing cells on their coordinates.
+    pub fn hashed(cell_size: S) -> Grid<S, B, T> {
+        Grid {
+            cell_size: cell_size,
+            storage: Storage::Hashed(HashMap::new()),
+            entries: HashMap::new(),
+            extent: None,
+        }
+    }
+
+    /// Create a grid covering `bounds` with a dense array of cells. Returns
+    /// `None` if the bounds are inverted or not finite, if the cell size is
+    /// not positive, or if the grid would have more than 2^24 cells.
+    pub fn uniform(bounds: B, cell_size: S) -> Option<Grid<S, B, T>> {
+        let (min, max) = (bounds.min(), bounds.max());
+        if !cell_size.is_finite() || cell_size <= S::zero() ||
+           (0..axes::<S, B::Point>()).any(|i| {
+               !min[i].is_finite() || !max[i].is_finite() || min[i] > max[i]
+           }) {
+            return None;
+        }
+        let lo: B::Cell = cell_of(min, cell_size);
+        let hi: B::Cell = cell_of(max, cell_size);
+        let count = cell_count(lo, hi);
+        if count > MAX_CELLS {
+            return None;
+        }
+        Some(Grid {
+            cell_size: cell_size,
+            storage: Storage::Uniform {
+                lo: lo,
+                hi: hi,
+                cells: (0..count).map(|_| Vec::new()).collect(),
+            },
+            entries: HashMap::new(),
+            extent: None,
+        })
+    }
+
+    /// The edge length of the grid's cells.
+    #[inline]
+    pub fn cell_size(&self) -> S { self.cell_size }
+
+    /// The number of handles stored in the grid.
+    #[inline]
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// Whether the grid stores no handles.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// The bound a handle was stored with.
+    #[inline]
+    pub fn get(&self, handle: &T) -> Option<&B> { self.entries.get(handle) }
+
+    /// Remove all handles from the grid.
+    pub fn clear(&mut self) {
+        match self.storage {
+            Storage::Hashed(ref mut cells) => cells.clear(),
+            Storage::Uniform { ref mut cells, .. } => for c in cells.iter_mut() { c.clear() },
+        }
+        self.entries.clear();
+    }
+
+    /// The cell containing the point `p`.
+    #[inline]
+    pub fn cell(&self, p: B::Point) -> B::Cell {
+        cell_of(p, self.cell_size)
+    }
+
+    /// Insert a handle with the given bound, returning the previous bound
+    /// if the handle was already present. Bounds that are not finite or
+    /// that cover too many cells are not stored, leaving the handle out of
+    /// the grid.
+    pub fn insert(&mut self, handle: T, bound: B) -> Option<B> {
+        let old = self.remove(&handle);
+        let (min, max) = (bound.min(), bound.max());
+        if (0..axes::<S, B::Point>()).any(|i| !min[i].is_finite() || !max[i].is_finite()) ||
+           cell_count(self.cell(min), self.cell(max)) > MAX_CELLS {
+            return old;
+        }
+        let (lo, hi) = self.cell_range(&bound);
+        for_each_cell(lo, hi, |c| self.cell_mut(c).push(handle));
+        self.entries.insert(handle, bound);
+        self.extent = Some(match self.extent {
+            Some(e) => e.union(&bound),
+            None => bound,
+        });
+        old
+    }
+
+    /// Remove a handle, returning its bound.
+    pub fn remove(&mut self, handle: &T) -> Option<B> {
+        let bound = match self.entries.remove(handle) {
+            Some(b) => b,
+            None => return None,
+        };
+        let (lo, hi) = self.cell_range(&bound);
+        for_each_cell(lo, hi, |c| self.remove_from_cell(c, handle));
+        Some(bound)
+    }
+
+    /// Move a stored handle to a new bound. Returns `false` if the handle is
+    /// not in the grid.
+    pub fn update(&mut self, handle: T, bound: B) -> bool {
+        if !self.entries.contains_key(&handle) {
+            return false;
+        }
+        self.insert(handle, bound);
+        true
+    }
+
+    /// Find all handles whose bounds overlap `bound`.
+    pub fn query_bound(&self, bound: &B) -> Vec<T> {
+        self.query(bound, |b| b.intersects(bound))
+    }
+
+    /// Find all handles whose bounds are within `radius` of `center`.
+    pub fn query_radius(&self, center: B::Point, radius: S) -> Vec<T> {
+        let r = B::Vector::from_value(radius);
+        let region = B::new(center + r * -S::one(), center + r);
+        self.query(&region, |b| b.distance2(center) <= radius * radius)
+    }
+
+    /// Visit the cells pierced by a ray, in order along the ray, up to the
+    /// ray parameter `max_t`, using the algorithm of Amanatides and Woo. The
+    /// visitor receives each cell with the handles stored in it, and stops
+    /// the traversal by returning `false`. Traversal is limited to the box
+    /// enclosing every bound stored so far. Cells beyond the border of a
+    /// uniform grid receive the handles of the border cell nearest to them.
+    pub fn traverse_ray<F>(&self, ray: &Ray<S, B::Point, B::Vector>, max_t: S, mut visit: F)
+        where F: FnMut(B::Cell, &[T]) -> bool
+    {
+        let extent = match self.extent {
+            Some(e) => e,
+            None => return,
+        };
+        let (t0, t1) = match extent.ray_span_with(ray, Boundary::Closed) {
+            Some(span) => (span.enter, span.exit.min(max_t)),
+            None => return,
+        };
+        let cells: RayCells<S, B::Point, B::Cell> =
+            RayCells::new(ray.origin, ray.direction, self.cell_size, t0, t1);
+        for cell in cells {
+            if !visit(cell, self.cell_handles(self.clamp(cell))) {
+                return;
+            }
+        }
+    }
+
+    /// Find all handles whose bounds are hit by a ray up to the ray parameter
+    /// `max_t`, in the order their cells are traversed.
+    pub fn query_ray(&self, ray: &Ray<S, B::Point, B::Vector>, max_t: S) -> Vec<T> {
+        let mut seen = HashSet::new();
+        let mut result = Vec::new();
+        self.traverse_ray(ray, max_t, |_, handles| {
+            for h in handles {
+                if seen.insert(*h) {
+                    let hit = self.entries[h].ray_span_with(ray, Boundary::Closed)
+                        .map_or(false, |span| span.enter <= max_t);
+                    if hit {
+                        result.push(*h);
+                    }
+                }
+            }
+            true
+        });
+        result
+    }
+
+    fn query<F>(&self, region: &B, filter: F) -> Vec<T>
+        where F: Fn(&B) -> bool
+    {
+        let mut seen = HashSet::new();
+        let mut result = Vec::new();
+        let (lo, hi) = self.cell_range(region);
+        let mut visit = |handles: &[T]| {
+            for h in handles {
+                if seen.insert(*h) && filter(&self.entries[h]) {
+                    result.push(*h);
+                }
+            }
+        };
+        match self.storage {
+            // walk the stored cells when the region covers more of them
+            Storage::Hashed(ref cells) if cell_count(lo, hi) > cells.len() as u64 => {
+                for (c, handles) in cells {
+                    if (0..axes::<i32, B::Cell>()).all(|i| lo[i] <= c[i] && c[i] <= hi[i]) {
+                        visit(handles);
+                    }
+                }
+            }
+            _ => for_each_cell(lo, hi, |c| visit(self.cell_handles(c))),
+        }
+        result
+    }
+
+    fn cell_range(&self, bound: &B) -> (B::Cell, B::Cell) {
+        (self.clamp(self.cell(bound.min())), self.clamp(self.cell(bound.max())))
+    }
+
+    /// The nearest cell of a uniform grid to `c`.
+    fn clamp(&self, mut c: B::Cell) -> B::Cell {
+        if let Storage::Uniform { lo, hi, .. } = self.storage {
+            for i in 0..axes::<i32, B::Cell>() {
+                c[i] = c[i].max(lo[i]).min(hi[i]);
+            }
+        }
+        c
+    }
+
+    fn cell_handles(&self, c: B::Cell) -> &[T] {
+        match self.storage {
+            Storage::Hashed(ref cells) => cells.get(&c).map_or(&[], |v| &v[..]),
+            Storage::Uniform { lo, hi, ref cells } => match linear_index(c, lo, hi) {
+                Some(i) => &cells[i],
+                None => &[],
+            },
+        }
+    }
+
+    fn remove_from_cell(&mut self, c: B::Cell, handle: &T) {
+        let empty = {
+            let cell = self.cell_mut(c);
+            if let Some(i) = cell.iter().position(|h| h == handle) {
+                cell.swap_remove(i);
+            }
+            cell.is_empty()
+        };
+        if let Storage::Hashed(ref mut cells) = self.storage {
+            if empty {
+                cells.remove(&c);
+            }
+        }
+    }
+
+    fn cell_mut(&mut self, c: B::Cell) -> &mut Vec<T> {
+        match self.storage {
+            Storage::Hashed(ref mut cells) => cells.entry(c).or_insert_with(Vec::new),
+            Storage::Uniform { lo, hi, ref mut cells } => {
+                let i = linear_index(c, lo, hi).expect("cell out of range");
+                &mut cells[i]
+            }
+        }
+    }
+}
+
+impl<S, T> Grid3<S, T>
+    where S: BaseFloat,
+          T: Copy + Eq + Hash
+{
+    /// Find all handles whose bounds overlap a sphere.
+    #[inline]
+    pub fn query_sphere(&self, sphere: &Sphere<S>) -> Vec<T> {
+        self.query_radius(sphere.center, sphere.radius)
+    }
+}
+
+fn cell_of<S: BaseFloat, P: Array<Element=S>, C: Array<Element=i32>>(p: P, cell_size: S) -> C {
+    let mut c = C::from_value(0);
+    for i in 0..axes::<i32, C>() {
+        // saturate coordinates beyond the range of cell indices
+        let x = (p[i] / cell_size).floor();
+        c[i] = x.to_i32().unwrap_or(if x > S::zero() { i32::MAX } else { i32::MIN });
+    }
+    c
+}
+
+/// The number of cells from `lo` to `hi`, saturating at `u64::MAX`.
+fn cell_count<C: Array<Element=i32>>(lo: C, hi: C) -> u64 {
+    let mut count: u64 = 1;
+    for i in 0..axes::<i32, C>() {
+        let n = (hi[i] as i64 - lo[i] as i64 + 1).max(0) as u64;
+        count = count.checked_mul(n).unwrap_or(u64::MAX);
+    }
+    count
+}
+
+fn linear_index<C: Array<Element=i32>>(c: C, lo: C, hi: C) -> Option<usize> {
+    let mut index = 0;
+    for i in (0..axes::<i32, C>()).rev() {
+        if c[i] < lo[i] || c[i] > hi[i] {
+            return None;
+        }
+        index = index * (hi[i] - lo[i] + 1) as usize + (c[i] - lo[i]) as usize;
+    }
+    Some(index)
+}
+
+fn for_each_cell<C, F>(lo: C, hi: C, mut f: F)
+    where C: Array<Element=i32> + Copy,
+          F: FnMut(C)
+{
+    let mut c = lo;
+    loop {
+        f(c);
+        let mut i = 0;
+        loop {
+            if i == axes::<i32, C>() {
+                return;
+            }
+            if c[i] < hi[i] {
+                c[i] += 1;
+                break;
+            }
+            c[i] = lo[i];
+            i += 1;
+        }
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Half-planes, the two-dimensional counterpart of planes
+
+use std::fmt;
+
+use cgmath::{BaseFloat, EuclideanSpace, InnerSpace};
+use cgmath::{Point2, Vector2, Zero};
+
+/// A line in the implicit form `A*x + B*y - D = 0`, bounding the half-plane
+/// its normal points to.
+///
+/// # Fields
+///
+/// - `n`: the normal of the line, `A` and `B` in the line equation
+/// - `d`: the distance value, `D` in the line equation
+#[derive(Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "rustc-serialize", derive(RustcEncodable, RustcDecodable))]
+pub struct HalfPlane<S> {
+    pub n: Vector2<S>,
+    pub d: S,
+}
+
+impl<S: BaseFloat> HalfPlane<S> {
+    /// Construct a half-plane from a normal vector and a scalar distance.
+    pub fn new(n: Vector2<S>, d: S) -> HalfPlane<S> {
+        HalfPlane { n: n, d: d }
+    }
+
+    /// Construct the half-plane to the left of the line running from `a` to
+    /// `b`, so that points in counter-clockwise order bound their inside.
+    /// Returns `None` if the points are the same.
+    pub fn from_points(a: Point2<S>, b: Point2<S>) -> Option<HalfPlane<S>> {
+        let v = b - a;
+        if ulps_eq!(v, &Vector2::zero()) {
+            return None;
+        }
+        let n = Vector2::new(-v.y, v.x).normalize();
+        Some(HalfPlane::new(n, n.dot(a.to_vec())))
+    }
+
+    /// Construct the half-plane bounded by the line through `p`,
+    /// perpendicular to `n`.
+    pub fn from_point_normal(p: Point2<S>, n: Vector2<S>) -> HalfPlane<S> {
+        HalfPlane { n: n, d: n.dot(p.to_vec()) }
+    }
+
+    /// Normalize the half-plane.
+    pub fn normalize(&self) -> Option<HalfPlane<S>> {
+        if ulps_eq!(self.n, &Vector2::zero()) {
+            return None;
+        }
+        let denom = S::one() / self.n.magnitude();
+        Some(HalfPlane::new(self.n * denom, self.d * denom))
+    }
+
+    /// The signed distance from the line to a point, positive inside. The
+    /// distance is scaled by the length of the normal if it is not a unit
+    /// vector.
+    #[inline]
+    pub fn distance(&self, p: Point2<S>) -> S {
+        self.n.dot(p.to_vec()) - self.d
+    }
+
+    /// The complement of the half-plane, sharing the same line.
+    #[inline]
+    pub fn flip(&self) -> HalfPlane<S> {
+        HalfPlane::new(-self.n, -self.d)
+    }
+}
+
+impl<S: BaseFloat> fmt::Debug for HalfPlane<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}x + {:?}y - {:?} >= 0", self.n.x, self.n.y, self.d)
+    }
+}
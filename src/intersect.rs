@@ -13,30 +13,91 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use {Ray2, Ray3, Plane, Line2};
+use {Ray2, Ray3, Plane, Line, Line2, Line3, LineIntersection, LineIntersection2};
 use cgmath::{BaseFloat, Zero, EuclideanSpace};
 use cgmath::{Point2, Point3};
-use cgmath::{InnerSpace, Vector2};
-
+use cgmath::{InnerSpace, Vector2, Vector3};
+
+/// Intersection between two objects.
+///
+/// All implementations follow the same rules for degenerate input:
+///
+/// - Any NaN component in either object gives `None`.
+/// - Rays and segments running parallel to a surface only hit it if they
+///   lie on it, at their origin.
+/// - A ray with a zero direction, or a segment of zero length, is a single
+///   point, which hits an object if it lies on or inside of it.
+/// - A ray starting inside of a solid hits its boundary where it leaves the
+///   solid, while a segment starting inside of a solid reports its origin.
+/// - Boundaries are closed: touching an object counts as hitting it.
 pub trait Intersect<Result> {
     fn intersection(&self) -> Result;
 }
 
+/// Whether any component of a vector is NaN.
+#[inline]
+pub fn is_nan2<S: BaseFloat>(v: Vector2<S>) -> bool {
+    v.x.is_nan() || v.y.is_nan()
+}
+
+/// Whether any component of a vector is NaN.
+#[inline]
+pub fn is_nan3<S: BaseFloat>(v: Vector3<S>) -> bool {
+    v.x.is_nan() || v.y.is_nan() || v.z.is_nan()
+}
+
+#[inline]
+fn plane_is_nan<S: BaseFloat>(p: &Plane<S>) -> bool {
+    is_nan3(p.n) || p.d.is_nan()
+}
 
 impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Plane<S>, Ray3<S>) {
     fn intersection(&self) -> Option<Point3<S>> {
         let (ref p, ref r) = *self;
 
-        let t = -(p.d + r.origin.dot(p.n)) / r.direction.dot(p.n);
+        if plane_is_nan(p) || is_nan3(r.origin.to_vec()) || is_nan3(r.direction) {
+            return None;
+        }
+        let dist = p.d - r.origin.dot(p.n);
+        let denom = r.direction.dot(p.n);
+        if denom == S::zero() {
+            // parallel, hits only if the ray lies on the plane
+            return if dist == S::zero() { Some(r.origin) } else { None };
+        }
+        let t = dist / denom;
         if t < Zero::zero() { None }
         else { Some(r.origin + r.direction * t) }
     }
 }
 
+/// See _Real-Time Collision Detection_, p. 176
+impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Plane<S>, Line3<S>) {
+    fn intersection(&self) -> Option<Point3<S>> {
+        let (ref p, ref l) = *self;
+
+        if plane_is_nan(p) || is_nan3(l.origin.to_vec()) || is_nan3(l.dest.to_vec()) {
+            return None;
+        }
+        let v = l.dest - l.origin;
+        let dist = p.d - p.n.dot(l.origin.to_vec());
+        let denom = p.n.dot(v);
+        if denom == S::zero() {
+            // parallel, hits only if the segment lies on the plane
+            return if dist == S::zero() { Some(l.origin) } else { None };
+        }
+        let t = dist / denom;
+        if t >= S::zero() && t <= S::one() { Some(l.origin + v * t) }
+        else { None }
+    }
+}
+
 /// See _Real-Time Collision Detection_, p. 210
 impl<S: BaseFloat> Intersect<Option<Ray3<S>>> for (Plane<S>, Plane<S>) {
     fn intersection(&self) -> Option<Ray3<S>> {
         let (p1, p2) = *self;
+        if plane_is_nan(&p1) || plane_is_nan(&p2) {
+            return None;
+        }
         let d = p1.n.cross(p2.n);
         let denom = d.dot(d);
         if ulps_eq!(denom, &S::zero()) {
@@ -53,6 +114,9 @@ impl<S: BaseFloat> Intersect<Option<Ray3<S>>> for (Plane<S>, Plane<S>) {
 impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Plane<S>, Plane<S>, Plane<S>) {
     fn intersection(&self) -> Option<Point3<S>> {
         let (p1, p2, p3) = *self;
+        if plane_is_nan(&p1) || plane_is_nan(&p2) || plane_is_nan(&p3) {
+            return None;
+        }
         let u = p2.n.cross(p3.n);
         let denom = p1.n.dot(u);
         if ulps_eq!(denom.abs(), &S::zero()) {
@@ -70,6 +134,15 @@ impl<S: BaseFloat> Intersect<Option<Point2<S>>> for (Ray2<S>, Line2<S>) {
     fn intersection(&self) -> Option<Point2<S>> {
         let (ref ray, ref line) = *self;
 
+        if is_nan2(ray.origin.to_vec()) || is_nan2(ray.direction) {
+            return None;
+        }
+        if ray.direction == Vector2::zero() {
+            // a single point, which has to lie on the segment
+            let point = Line::new(ray.origin, ray.origin);
+            return (point, *line).intersection().map(|_| ray.origin);
+        }
+
         let p = ray.origin;
         let q = line.origin;
         let r = ray.direction;
@@ -115,3 +188,67 @@ impl<S: BaseFloat> Intersect<Option<Point2<S>>> for (Ray2<S>, Line2<S>) {
         return None;
     }
 }
+
+/// Determines if two line segments intersect. Collinear segments that
+/// overlap report the shared sub-segment, directed like the first segment.
+///
+/// See _Real-Time Collision Detection_, p. 151 - 153
+impl<S: BaseFloat> Intersect<Option<LineIntersection2<S>>> for (Line2<S>, Line2<S>) {
+    fn intersection(&self) -> Option<LineIntersection2<S>> {
+        let (ref a, ref b) = *self;
+
+        if is_nan2(a.origin.to_vec()) || is_nan2(a.dest.to_vec()) ||
+           is_nan2(b.origin.to_vec()) || is_nan2(b.dest.to_vec()) {
+            return None;
+        }
+
+        let p = a.origin;
+        let q = b.origin;
+        let r = a.dest - a.origin;
+        let s = b.dest - b.origin;
+
+        let cross_1 = r.perp_dot(s);
+        let qmp = q - p;
+        let cross_2 = qmp.perp_dot(r);
+
+        if cross_1 == S::zero() {
+            if cross_2 != S::zero() {
+                // parallel
+                return None;
+            }
+
+            let rr = r.dot(r);
+            if rr == S::zero() {
+                // the first segment is a single point
+                if s.dot(s) == S::zero() {
+                    return if p == q { Some(LineIntersection::Point(p)) } else { None };
+                }
+                return (*b, *a).intersection().map(|_| LineIntersection::Point(p));
+            }
+
+            // collinear, express the second segment in terms of the first
+            let t0 = qmp.dot(r) / rr;
+            let t1 = t0 + s.dot(r) / rr;
+            let lo = t0.min(t1).max(S::zero());
+            let hi = t0.max(t1).min(S::one());
+
+            if lo > hi {
+                // no overlap exists
+                None
+            } else if lo == hi {
+                Some(LineIntersection::Point(p + r * lo))
+            } else {
+                Some(LineIntersection::Overlap(Line::new(p + r * lo, p + r * hi)))
+            }
+        } else {
+            let t = qmp.perp_dot(s) / cross_1;
+            let u = cross_2 / cross_1;
+
+            if t >= S::zero() && t <= S::one() && u >= S::zero() && u <= S::one() {
+                Some(LineIntersection::Point(p + r * t))
+            } else {
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Static k-d trees for point queries
+//!
+//! The tree is stored implicitly: the point indices are permuted so that
+//! every sub-range is split at its median, alternating the axis with depth.
+
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use cgmath::{Array, BaseFloat, ElementWise, EuclideanSpace};
+use cgmath::{Point2, Point3};
+
+use {Aabb, Boundary};
+use aabb::axes;
+
+/// A static k-d tree over a set of points, answering queries with indices
+/// into the slice the tree was built from.
+pub struct KdTree<S, P> {
+    points: Vec<P>,
+    indices: Vec<usize>,
+    phantom_s: PhantomData<S>,
+}
+
+/// A k-d tree of two-dimensional points.
+pub type KdTree2<S> = KdTree<S, Point2<S>>;
+/// A k-d tree of three-dimensional points.
+pub type KdTree3<S> = KdTree<S, Point3<S>>;
+
+impl<S, P> KdTree<S, P>
+    where S: BaseFloat,
+          P: EuclideanSpace<Scalar=S>,
+          P::Diff: ElementWise + Array<Element=S>
+{
+    /// Build a tree from a slice of points.
+    pub fn new(points: &[P]) -> KdTree<S, P> {
+        let mut indices: Vec<usize> = (0..points.len()).collect();
+        build(points, &mut indices, 0);
+        KdTree {
+            points: points.to_vec(),
+            indices: indices,
+            phantom_s: PhantomData,
+        }
+    }
+
+    /// The number of points in the tree.
+    #[inline]
+    pub fn len(&self) -> usize { self.points.len() }
+
+    /// Whether the tree contains no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.points.is_empty() }
+
+    /// The point with the given index.
+    #[inline]
+    pub fn point(&self, index: usize) -> P { self.points[index] }
+
+    /// Find the point nearest to `p`, returning its index and squared
+    /// distance.
+    pub fn nearest(&self, p: P) -> Option<(usize, S)> {
+        self.k_nearest(p, 1).pop()
+    }
+
+    /// Find the `k` points nearest to `p`, returning their indices and
+    /// squared distances ordered from nearest to farthest.
+    pub fn k_nearest(&self, p: P, k: usize) -> Vec<(usize, S)> {
+        let mut best = Vec::with_capacity(k + 1);
+        if k > 0 {
+            self.k_nearest_in(0, self.indices.len(), 0, p, k, &mut best);
+        }
+        best
+    }
+
+    /// Find the indices of all points within `radius` of `p`.
+    pub fn within_radius(&self, p: P, radius: S) -> Vec<usize> {
+        let mut result = Vec::new();
+        self.within_radius_in(0, self.indices.len(), 0, p, radius * radius, &mut result);
+        result
+    }
+
+    /// Find the indices of all points inside a box, including its boundary.
+    pub fn range<B: Aabb<S, P::Diff, P>>(&self, bound: &B) -> Vec<usize> {
+        let mut result = Vec::new();
+        self.range_in(0, self.indices.len(), 0, bound, &mut result);
+        result
+    }
+
+    fn k_nearest_in(&self, lo: usize, hi: usize, depth: usize,
+                    p: P, k: usize, best: &mut Vec<(usize, S)>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let index = self.indices[mid];
+        let q = self.points[index];
+
+        let d2 = distance2(p, q);
+        if best.len() < k || d2 < best[best.len() - 1].1 {
+            let at = best.iter().position(|b| d2 < b.1).unwrap_or(best.len());
+            best.insert(at, (index, d2));
+            best.truncate(k);
+        }
+
+        let axis = depth % axes::<S, P>();
+        let diff = p[axis] - q[axis];
+        let (near, far) = if diff < S::zero() {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.k_nearest_in(near.0, near.1, depth + 1, p, k, best);
+        if best.len() < k || diff * diff < best[best.len() - 1].1 {
+            self.k_nearest_in(far.0, far.1, depth + 1, p, k, best);
+        }
+    }
+
+    fn within_radius_in(&self, lo: usize, hi: usize, depth: usize,
+                        p: P, radius2: S, result: &mut Vec<usize>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let index = self.indices[mid];
+        let q = self.points[index];
+
+        if distance2(p, q) <= radius2 {
+            result.push(index);
+        }
+
+        let axis = depth % axes::<S, P>();
+        let diff = p[axis] - q[axis];
+        if diff <= S::zero() || diff * diff <= radius2 {
+            self.within_radius_in(lo, mid, depth + 1, p, radius2, result);
+        }
+        if diff >= S::zero() || diff * diff <= radius2 {
+            self.within_radius_in(mid + 1, hi, depth + 1, p, radius2, result);
+        }
+    }
+
+    fn range_in<B: Aabb<S, P::Diff, P>>(&self, lo: usize, hi: usize, depth: usize,
+                                         bound: &B, result: &mut Vec<usize>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let index = self.indices[mid];
+        let q = self.points[index];
+        let (min, max) = (bound.min(), bound.max());
+
+        if bound.contains_with(q, Boundary::Closed) {
+            result.push(index);
+        }
+
+        let axis = depth % axes::<S, P>();
+        if min[axis] <= q[axis] {
+            self.range_in(lo, mid, depth + 1, bound, result);
+        }
+        if max[axis] >= q[axis] {
+            self.range_in(mid + 1, hi, depth + 1, bound, result);
+        }
+    }
+}
+
+fn build<S: BaseFloat, P: Array<Element=S>>(points: &[P], indices: &mut [usize], depth: usize) {
+    if indices.len() <= 1 {
+        return;
+    }
+    let axis = depth % axes::<S, P>();
+    indices.sort_by(|&a, &b| {
+        points[a][axis].partial_cmp(&points[b][axis]).unwrap_or(Ordering::Equal)
+    });
+    let mid = indices.len() / 2;
+    let (left, right) = indices.split_at_mut(mid);
+    build(points, left, depth + 1);
+    build(points, &mut right[1..], depth + 1);
+}
+
+fn distance2<S: BaseFloat, P: Array<Element=S>>(a: P, b: P) -> S {
+    let mut d2 = S::zero();
+    for i in 0..axes::<S, P>() {
+        let d = a[i] - b[i];
+        d2 = d2 + d * d;
+    }
+    d2
+}
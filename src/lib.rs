@@ -40,26 +40,52 @@ extern crate num;
 extern crate rustc_serialize;
 
 // Re-exports
-pub use aabb::*;
+pub use aabb::{Aabb, Aabb2, Aabb3, Boundary, MinMax};
 pub use bound::*;
+pub use cascade::{Cascade, SplitScheme};
+pub use cells::{Cells, RayCells};
+pub use circle::Circle;
 pub use cylinder::Cylinder;
-pub use frustum::{Frustum, FrustumPoints, Projection};
+pub use frustum::{DepthRange, Frustum, FrustumPoints, PlaneMask, Projection};
+pub use frustum2::Frustum2;
+pub use grid::{Grid, Grid2, Grid3, GridBound};
+pub use half_plane::HalfPlane;
 pub use intersect::Intersect;
+pub use kdtree::{KdTree, KdTree2, KdTree3};
 pub use obb::*;
+pub use occlusion::OcclusionBuffer;
+pub use octree::{LooseTree, Quadtree, Octree};
+pub use overlap::{Overlap, Penetration};
+pub use span::{RaySpan, Span, SpanSet};
 pub use sphere::Sphere;
 pub use plane::Plane;
+pub use polygon::{ConvexPolygon, ConvexPolyhedron};
+pub use portal::{Portal, PortalFrustum, PortalGraph};
 pub use ray::{Ray, Ray2, Ray3};
-pub use line::{Line2, Line3};
+pub use line::{Line, Line2, Line3, LineIntersection, LineIntersection2};
 
 // Modules
 
 mod aabb;
 mod bound;
+mod cascade;
+mod cells;
+mod circle;
 mod cylinder;
 mod frustum;
+mod frustum2;
+mod grid;
+mod half_plane;
 mod intersect;
+mod kdtree;
 mod obb;
+mod occlusion;
+mod octree;
+mod overlap;
+mod span;
 mod sphere;
 mod plane;
+mod polygon;
+mod portal;
 mod ray;
 mod line;
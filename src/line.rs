@@ -15,11 +15,14 @@
 
 //! Line segments
 
+use std::fmt;
 use std::marker::PhantomData;
 
-use cgmath::{BaseNum};
+use cgmath::{BaseNum, BaseFloat};
 use cgmath::{EuclideanSpace, Point2, Point3};
-use cgmath::{VectorSpace, Vector2, Vector3};
+use cgmath::{VectorSpace, InnerSpace, Vector2, Vector3};
+
+use {Aabb2, Plane, Ray};
 
 /// A generic directed line segment from `origin` to `dest`.
 #[derive(Copy, Clone, PartialEq)]
@@ -42,5 +45,201 @@ impl<S: BaseNum, V: VectorSpace<Scalar=S>, P: EuclideanSpace<Scalar=S, Diff=V>>
     }
 }
 
+impl<S, V, P> Line<S, V, P>
+    where S: BaseFloat,
+          V: InnerSpace<Scalar=S>,
+          P: EuclideanSpace<Scalar=S, Diff=V>
+{
+    /// The vector from `origin` to `dest`.
+    #[inline]
+    pub fn vector(&self) -> V { self.dest - self.origin }
+
+    /// The length of the segment.
+    #[inline]
+    pub fn length(&self) -> S { self.vector().magnitude() }
+
+    /// The unit direction from `origin` to `dest`.
+    #[inline]
+    pub fn direction(&self) -> V { self.vector().normalize() }
+
+    /// The point at parameter `t`, where `0` is the origin and `1` is the
+    /// destination.
+    #[inline]
+    pub fn point_at(&self, t: S) -> P { self.origin + self.vector() * t }
+
+    /// The parameter in `[0, 1]` of the point on the segment closest to `p`.
+    ///
+    /// See _Real-Time Collision Detection_, p. 127 - 130
+    pub fn closest_param(&self, p: P) -> S {
+        let v = self.vector();
+        let len2 = v.magnitude2();
+        if len2 == S::zero() {
+            return S::zero();
+        }
+        let t = (p - self.origin).dot(v) / len2;
+        t.max(S::zero()).min(S::one())
+    }
+
+    /// The point on the segment closest to `p`.
+    #[inline]
+    pub fn closest_point(&self, p: P) -> P { self.point_at(self.closest_param(p)) }
+
+    /// Convert the segment into a ray with a unit direction, together with
+    /// the ray parameter of `dest`. The segment covers the ray's parameter
+    /// range `[0, length]`. A degenerate segment yields a zero direction.
+    pub fn to_ray(&self) -> (Ray<S, P, V>, S) {
+        let length = self.length();
+        let direction = if length == S::zero() {
+            V::zero()
+        } else {
+            self.vector() / length
+        };
+        (Ray::new(self.origin, direction), length)
+    }
+}
+
+impl<S: BaseFloat> Line3<S> {
+    /// Split the segment by a plane into the part in front of the plane (on
+    /// the side the normal points to) and the part behind it. Either part is
+    /// `None` if the segment does not reach that side.
+    pub fn split_plane(&self, plane: &Plane<S>) -> (Option<Line3<S>>, Option<Line3<S>>) {
+        let da = plane.distance(self.origin);
+        let db = plane.distance(self.dest);
+
+        if da >= S::zero() && db >= S::zero() {
+            (Some(*self), None)
+        } else if da <= S::zero() && db <= S::zero() {
+            (None, Some(*self))
+        } else {
+            let mid = self.point_at(da / (da - db));
+            if da > S::zero() {
+                (Some(Line::new(self.origin, mid)), Some(Line::new(mid, self.dest)))
+            } else {
+                (Some(Line::new(mid, self.dest)), Some(Line::new(self.origin, mid)))
+            }
+        }
+    }
+
+    /// Find the intersection of the segment with the triangle `abc`.
+    pub fn intersect_triangle(&self, a: Point3<S>, b: Point3<S>, c: Point3<S>) -> Option<Point3<S>> {
+        let (ray, length) = self.to_ray();
+        ray.intersect_triangle(a, b, c).and_then(|p| {
+            if (p - self.origin).magnitude2() <= length * length { Some(p) } else { None }
+        })
+    }
+}
+
+impl<S, V, P: fmt::Debug> fmt::Debug for Line<S, V, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{:?} -> {:?}]", self.origin, self.dest)
+    }
+}
+
 pub type Line2<S> = Line<S, Vector2<S>, Point2<S>>;
 pub type Line3<S> = Line<S, Vector3<S>, Point3<S>>;
+
+/// The result of intersecting two line segments.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LineIntersection<S, V, P> {
+    /// The segments meet in a single point.
+    Point(P),
+    /// The segments are collinear and share the given sub-segment.
+    Overlap(Line<S, V, P>),
+}
+
+pub type LineIntersection2<S> = LineIntersection<S, Vector2<S>, Point2<S>>;
+
+// Cohen–Sutherland outcodes
+const INSIDE: u8 = 0;
+const LEFT: u8 = 1;
+const RIGHT: u8 = 2;
+const BOTTOM: u8 = 4;
+const TOP: u8 = 8;
+
+fn outcode<S: BaseFloat>(p: Point2<S>, aabb: &Aabb2<S>) -> u8 {
+    let mut code = INSIDE;
+    if p.x < aabb.min.x { code |= LEFT; }
+    else if p.x > aabb.max.x { code |= RIGHT; }
+    if p.y < aabb.min.y { code |= BOTTOM; }
+    else if p.y > aabb.max.y { code |= TOP; }
+    code
+}
+
+impl<S: BaseFloat> Line2<S> {
+    /// Clip the segment against a closed box using the Liang–Barsky
+    /// algorithm, returning the part of the segment inside the box, if any.
+    /// The clipped segment keeps the direction of the original one.
+    pub fn clip_liang_barsky(&self, aabb: &Aabb2<S>) -> Option<Line2<S>> {
+        let d = self.dest - self.origin;
+        let mut t0 = S::zero();
+        let mut t1 = S::one();
+
+        let edges = [
+            (-d.x, self.origin.x - aabb.min.x),
+            ( d.x, aabb.max.x - self.origin.x),
+            (-d.y, self.origin.y - aabb.min.y),
+            ( d.y, aabb.max.y - self.origin.y),
+        ];
+
+        for &(p, q) in edges.iter() {
+            if p.is_nan() || q.is_nan() {
+                return None;
+            }
+            if p == S::zero() {
+                // parallel to this edge, and outside of it
+                if q < S::zero() { return None; }
+            } else {
+                let r = q / p;
+                if p < S::zero() {
+                    if r > t1 { return None; }
+                    if r > t0 { t0 = r; }
+                } else {
+                    if r < t0 { return None; }
+                    if r < t1 { t1 = r; }
+                }
+            }
+        }
+
+        Some(Line::new(self.origin + d * t0, self.origin + d * t1))
+    }
+
+    /// Clip the segment against a closed box using the Cohen–Sutherland
+    /// algorithm, returning the part of the segment inside the box, if any.
+    /// The clipped segment keeps the direction of the original one.
+    pub fn clip_cohen_sutherland(&self, aabb: &Aabb2<S>) -> Option<Line2<S>> {
+        let mut p0 = self.origin;
+        let mut p1 = self.dest;
+        let mut c0 = outcode(p0, aabb);
+        let mut c1 = outcode(p1, aabb);
+
+        loop {
+            if c0 | c1 == INSIDE {
+                return Some(Line::new(p0, p1));
+            }
+            if c0 & c1 != INSIDE {
+                return None;
+            }
+
+            // at least one end point is outside, move it onto the box edge
+            let c = if c0 != INSIDE { c0 } else { c1 };
+            let d = p1 - p0;
+            let p = if c & TOP != INSIDE {
+                Point2::new(p0.x + d.x * (aabb.max.y - p0.y) / d.y, aabb.max.y)
+            } else if c & BOTTOM != INSIDE {
+                Point2::new(p0.x + d.x * (aabb.min.y - p0.y) / d.y, aabb.min.y)
+            } else if c & RIGHT != INSIDE {
+                Point2::new(aabb.max.x, p0.y + d.y * (aabb.max.x - p0.x) / d.x)
+            } else {
+                Point2::new(aabb.min.x, p0.y + d.y * (aabb.min.x - p0.x) / d.x)
+            };
+
+            if c == c0 {
+                p0 = p;
+                c0 = outcode(p0, aabb);
+            } else {
+                p1 = p;
+                c1 = outcode(p1, aabb);
+            }
+        }
+    }
+}
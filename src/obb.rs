@@ -15,9 +15,13 @@
 
 //! Oriented bounding boxes
 
+use cgmath::{BaseFloat, EuclideanSpace, InnerSpace, Matrix3, Rad};
 use cgmath::{Point2, Point3};
 use cgmath::{Vector2, Vector3};
 
+use {Aabb3, Ray3};
+use span::{RaySpan, Span};
+
 #[derive(Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "rustc-serialize", derive(RustcEncodable, RustcDecodable))]
 pub struct Obb2<S> {
@@ -30,6 +34,50 @@ pub struct Obb2<S> {
 #[cfg_attr(feature = "rustc-serialize", derive(RustcEncodable, RustcDecodable))]
 pub struct Obb3<S> {
     pub center: Point3<S>,
+    /// The rotation of the box from world space, as an axis scaled by the
+    /// angle of rotation in radians.
     pub axis: Vector3<S>,
+    /// The half-widths of the box along its local axes.
     pub extents: Vector3<S>,
 }
+
+impl<S: BaseFloat> Obb3<S> {
+    /// Construct a new oriented bounding box.
+    pub fn new(center: Point3<S>, axis: Vector3<S>, extents: Vector3<S>) -> Obb3<S> {
+        Obb3 {
+            center: center,
+            axis: axis,
+            extents: extents,
+        }
+    }
+
+    /// The unit directions of the local x, y and z axes of the box.
+    pub fn axes(&self) -> [Vector3<S>; 3] {
+        let angle = self.axis.magnitude();
+        if angle == S::zero() {
+            return [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()];
+        }
+        let m = Matrix3::from_axis_angle(self.axis / angle, Rad(angle));
+        [m.x, m.y, m.z]
+    }
+
+    /// Return the corners of the box, in the same order as
+    /// `Aabb3::to_corners` for an unrotated box.
+    pub fn to_corners(&self) -> [Point3<S>; 8] {
+        let a = self.axes();
+        let (x, y, z) = (a[0] * self.extents.x, a[1] * self.extents.y, a[2] * self.extents.z);
+        let c = self.center;
+        [c + (-x - y - z), c + (x - y - z), c + (-x + y - z), c + (x + y - z),
+         c + (-x - y + z), c + (x - y + z), c + (-x + y + z), c + (x + y + z)]
+    }
+}
+
+/// Clipped as a ray in the local frame of the box.
+impl<S: BaseFloat> RaySpan<S, Ray3<S>> for Obb3<S> {
+    fn ray_span(&self, ray: &Ray3<S>) -> Option<Span<S>> {
+        let axes = self.axes();
+        let local = |v: Vector3<S>| Vector3::new(axes[0].dot(v), axes[1].dot(v), axes[2].dot(v));
+        let ray = Ray3::new(Point3::from_vec(local(ray.origin - self.center)), local(ray.direction));
+        Aabb3::new(Point3::from_vec(-self.extents), Point3::from_vec(self.extents)).ray_span(&ray)
+    }
+}
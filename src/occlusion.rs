@@ -0,0 +1,248 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Software occlusion culling
+//!
+//! Occluders are rasterized into a small depth buffer on the CPU, from which
+//! a hierarchy of ever coarser levels is built, each texel keeping the
+//! farthest depth of the texels it covers. A bound is occluded if it lies
+//! behind the farthest depth everywhere it covers on the screen.
+
+use std::cmp::max;
+
+use cgmath::{BaseFloat, Matrix4, Point3, Vector3, Vector4};
+
+use {Aabb3, DepthRange, Sphere};
+use bound::Relation;
+
+/// A low resolution depth buffer for occlusion culling.
+pub struct OcclusionBuffer<S> {
+    width: usize,
+    height: usize,
+    view_proj: Matrix4<S>,
+    depth: DepthRange,
+    levels: Vec<Vec<S>>,
+    sizes: Vec<(usize, usize)>,
+    dirty: bool,
+}
+
+/// A point projected to the screen, with its depth increasing away from the
+/// eye whatever the depth range.
+#[derive(Copy, Clone)]
+struct Projected<S> {
+    x: S,
+    y: S,
+    depth: S,
+}
+
+impl<S: BaseFloat> OcclusionBuffer<S> {
+    /// Create an empty buffer of `width` by `height` texels, seeing the
+    /// world through the view-projection matrix `view_proj` with the given
+    /// depth range.
+    pub fn new(width: usize, height: usize, view_proj: Matrix4<S>, depth: DepthRange) -> OcclusionBuffer<S> {
+        let mut sizes = vec![(max(width, 1), max(height, 1))];
+        while sizes[sizes.len() - 1] != (1, 1) {
+            let (w, h) = sizes[sizes.len() - 1];
+            sizes.push(((w + 1) / 2, (h + 1) / 2));
+        }
+        OcclusionBuffer {
+            width: sizes[0].0,
+            height: sizes[0].1,
+            view_proj: view_proj,
+            depth: depth,
+            levels: sizes.iter().map(|&(w, h)| vec![S::infinity(); w * h]).collect(),
+            sizes: sizes,
+            dirty: false,
+        }
+    }
+
+    /// The width of the buffer in texels.
+    #[inline]
+    pub fn width(&self) -> usize { self.width }
+
+    /// The height of the buffer in texels.
+    #[inline]
+    pub fn height(&self) -> usize { self.height }
+
+    /// Remove all occluders, and start seeing the world through a new
+    /// view-projection matrix.
+    pub fn clear(&mut self, view_proj: Matrix4<S>) {
+        self.view_proj = view_proj;
+        for level in self.levels.iter_mut() {
+            for d in level.iter_mut() {
+                *d = S::infinity();
+            }
+        }
+        self.dirty = false;
+    }
+
+    /// The depth stored at a texel of the full resolution buffer, in
+    /// normalized device coordinates. Empty texels read as the depth of
+    /// the far plane.
+    pub fn depth(&self, x: usize, y: usize) -> S {
+        let d = self.levels[0][y * self.width + x];
+        if d == S::infinity() {
+            self.depth.near_far().1
+        } else if self.depth == DepthRange::ReversedZeroToOne {
+            -d
+        } else {
+            d
+        }
+    }
+
+    /// Rasterize an occluding triangle. Triangles crossing the near plane
+    /// are skipped, which may miss occlusion but never hides anything.
+    pub fn add_triangle(&mut self, a: Point3<S>, b: Point3<S>, c: Point3<S>) {
+        let (a, b, c) = match (self.project(a), self.project(b), self.project(c)) {
+            (Some(a), Some(b), Some(c)) => (a, b, c),
+            _ => return,
+        };
+        let area = edge(a, b, c.x, c.y);
+        if area == S::zero() {
+            return;
+        }
+        let (x0, x1) = self.span(a.x.min(b.x).min(c.x), a.x.max(b.x).max(c.x), self.width);
+        let (y0, y1) = self.span(a.y.min(b.y).min(c.y), a.y.max(b.y).max(c.y), self.height);
+        let half = S::one() / (S::one() + S::one());
+        for y in y0..y1 {
+            let py = S::from(y).unwrap() + half;
+            for x in x0..x1 {
+                let px = S::from(x).unwrap() + half;
+                let (wa, wb, wc) = (edge(b, c, px, py) / area, edge(c, a, px, py) / area, edge(a, b, px, py) / area);
+                if wa < S::zero() || wb < S::zero() || wc < S::zero() {
+                    continue;
+                }
+                let d = wa * a.depth + wb * b.depth + wc * c.depth;
+                let texel = &mut self.levels[0][y * self.width + x];
+                if d < *texel {
+                    *texel = d;
+                }
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Rasterize the faces of a solid occluding box.
+    pub fn add_aabb(&mut self, aabb: &Aabb3<S>) {
+        let c = aabb.to_corners();
+        let faces = [(0, 1, 3, 2), (4, 5, 7, 6), (0, 1, 5, 4),
+                     (2, 3, 7, 6), (0, 2, 6, 4), (1, 3, 7, 5)];
+        for &(i, j, k, l) in faces.iter() {
+            self.add_triangle(c[i], c[j], c[k]);
+            self.add_triangle(c[i], c[k], c[l]);
+        }
+    }
+
+    /// Rebuild the coarser levels of the buffer after adding occluders. Until
+    /// then, tests fall back to the full resolution buffer.
+    pub fn build(&mut self) {
+        for k in 1..self.levels.len() {
+            let (w, h) = self.sizes[k];
+            let (fw, fh) = self.sizes[k - 1];
+            for y in 0..h {
+                for x in 0..w {
+                    let mut d = S::neg_infinity();
+                    for &(fx, fy) in [(2 * x, 2 * y), (2 * x + 1, 2 * y), (2 * x, 2 * y + 1), (2 * x + 1, 2 * y + 1)].iter() {
+                        if fx < fw && fy < fh {
+                            d = d.max(self.levels[k - 1][fy * fw + fx]);
+                        }
+                    }
+                    self.levels[k][y * w + x] = d;
+                }
+            }
+        }
+        self.dirty = false;
+    }
+
+    /// Test whether a box is hidden behind the occluders. Returns `Out` if it
+    /// is occluded or off the screen, `In` if it may be visible, and `Cross`
+    /// if it crosses the near plane and cannot be tested.
+    pub fn test_aabb(&self, aabb: &Aabb3<S>) -> Relation {
+        let mut points = [Projected { x: S::zero(), y: S::zero(), depth: S::zero() }; 8];
+        for (p, c) in points.iter_mut().zip(aabb.to_corners().iter()) {
+            *p = match self.project(*c) { Some(p) => p, None => return Relation::Cross };
+        }
+        let (min_x, max_x) = points.iter().fold((S::infinity(), S::neg_infinity()), |(lo, hi), p| (lo.min(p.x), hi.max(p.x)));
+        let (min_y, max_y) = points.iter().fold((S::infinity(), S::neg_infinity()), |(lo, hi), p| (lo.min(p.y), hi.max(p.y)));
+        let nearest = points.iter().fold(S::infinity(), |d, p| d.min(p.depth));
+
+        let (x0, x1) = self.span(min_x, max_x, self.width);
+        let (y0, y1) = self.span(min_y, max_y, self.height);
+        if x0 >= x1 || y0 >= y1 {
+            return Relation::Out;
+        }
+
+        // pick the finest level where the box covers at most 2x2 texels
+        let mut k = 0;
+        if !self.dirty {
+            while k + 1 < self.levels.len() &&
+                  (((x1 - 1) >> k) - (x0 >> k) > 1 || ((y1 - 1) >> k) - (y0 >> k) > 1) {
+                k += 1;
+            }
+        }
+        let w = self.sizes[k].0;
+        for y in (y0 >> k)..((y1 - 1) >> k) + 1 {
+            for x in (x0 >> k)..((x1 - 1) >> k) + 1 {
+                if self.levels[k][y * w + x] >= nearest {
+                    return Relation::In;
+                }
+            }
+        }
+        Relation::Out
+    }
+
+    /// Test whether a sphere is hidden behind the occluders, as by
+    /// `test_aabb` for the box enclosing it.
+    pub fn test_sphere(&self, sphere: &Sphere<S>) -> Relation {
+        let r = Vector3::new(sphere.radius, sphere.radius, sphere.radius);
+        self.test_aabb(&Aabb3::new(sphere.center + (-r), sphere.center + r))
+    }
+
+    /// Project a point to texel coordinates, returning `None` if it is
+    /// behind the near plane.
+    fn project(&self, p: Point3<S>) -> Option<Projected<S>> {
+        let clip = self.view_proj * Vector4::new(p.x, p.y, p.z, S::one());
+        if clip.w <= S::zero() {
+            return None;
+        }
+        let z = clip.z / clip.w;
+        let (near, _) = self.depth.near_far::<S>();
+        let depth = if self.depth == DepthRange::ReversedZeroToOne { -z } else { z };
+        let near = if self.depth == DepthRange::ReversedZeroToOne { -near } else { near };
+        if depth < near {
+            return None;
+        }
+        let half = S::one() / (S::one() + S::one());
+        Some(Projected {
+            x: (clip.x / clip.w * half + half) * S::from(self.width).unwrap(),
+            y: (clip.y / clip.w * half + half) * S::from(self.height).unwrap(),
+            depth: depth,
+        })
+    }
+
+    /// The range of texels between two texel coordinates, clamped to the
+    /// buffer.
+    fn span(&self, lo: S, hi: S, size: usize) -> (usize, usize) {
+        let size_s = S::from(size).unwrap();
+        let lo = lo.floor().max(S::zero()).min(size_s).to_usize().unwrap_or(0);
+        let hi = hi.ceil().max(S::zero()).min(size_s).to_usize().unwrap_or(0);
+        (lo, hi)
+    }
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `(x, y)`.
+fn edge<S: BaseFloat>(a: Projected<S>, b: Projected<S>, x: S, y: S) -> S {
+    (b.x - a.x) * (y - a.y) - (b.y - a.y) * (x - a.x)
+}
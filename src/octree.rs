@@ -0,0 +1,316 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loose quadtrees and octrees
+//!
+//! Every node of a loose tree owns a cell of its parent, but accepts any
+//! bound whose center lies in the cell and which fits in the cell enlarged
+//! by the loose factor. This keeps objects from getting stuck high up in the
+//! tree just because they straddle a cell boundary.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use cgmath::BaseFloat;
+
+use {Aabb, Aabb2, Aabb3, Boundary, Frustum, PlaneMask, Ray, Sphere};
+use aabb::axes;
+use bound::Relation;
+use grid::GridBound;
+
+struct Node<B, T> {
+    cell: B,
+    children: Option<usize>,
+    items: Vec<T>,
+    /// The frustum plane that last rejected the node.
+    last_out: usize,
+}
+
+/// A loose tree partitioning space into nested cells, storing handles by
+/// their bounds.
+///
+/// Bounds whose center lies outside of the root cell are kept in the root.
+pub struct LooseTree<S: BaseFloat, B, T> {
+    nodes: Vec<Node<B, T>>,
+    entries: HashMap<T, (B, usize, usize)>,
+    root_extent: B,
+    max_depth: usize,
+    looseness: S,
+}
+
+/// A loose quadtree of two-dimensional boxes.
+pub type Quadtree<S, T> = LooseTree<S, Aabb2<S>, T>;
+/// A loose octree of three-dimensional boxes.
+pub type Octree<S, T> = LooseTree<S, Aabb3<S>, T>;
+
+impl<S, B, T> LooseTree<S, B, T>
+    where S: BaseFloat,
+          B: GridBound<S> + Aabb<S, B::Vector, B::Point>,
+          T: Copy + Eq + Hash
+{
+    /// Create a tree covering `bounds`, subdividing at most `max_depth`
+    /// times. Each node's cell is enlarged by `looseness` (at least `1`) to
+    /// find the bounds it accepts; `2` is a common choice.
+    pub fn new(bounds: B, max_depth: usize, looseness: S) -> LooseTree<S, B, T> {
+        let mut tree = LooseTree {
+            nodes: vec![Node { cell: bounds, children: None, items: Vec::new(), last_out: 0 }],
+            entries: HashMap::new(),
+            root_extent: bounds,
+            max_depth: max_depth,
+            looseness: looseness.max(S::one()),
+        };
+        tree.root_extent = tree.loose(&bounds);
+        tree
+    }
+
+    /// The number of handles stored in the tree.
+    #[inline]
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// Whether the tree stores no handles.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// The bound a handle was stored with.
+    #[inline]
+    pub fn get(&self, handle: &T) -> Option<&B> { self.entries.get(handle).map(|e| &e.0) }
+
+    /// Remove all handles from the tree.
+    pub fn clear(&mut self) {
+        self.nodes.truncate(1);
+        self.nodes[0].children = None;
+        self.nodes[0].items.clear();
+        self.entries.clear();
+        self.root_extent = self.loose(&self.nodes[0].cell);
+    }
+
+    /// Insert a handle with the given bound, returning the previous bound
+    /// if the handle was already present.
+    pub fn insert(&mut self, handle: T, bound: B) -> Option<B> {
+        let old = self.remove(&handle);
+        let center = bound.center();
+
+        let mut node = 0;
+        for _ in 0..self.max_depth {
+            let mid = self.nodes[node].cell.center();
+            let mut index = 0;
+            for i in 0..axes::<S, B::Point>() {
+                if center[i] >= mid[i] {
+                    index |= 1 << i;
+                }
+            }
+            let child = child_cell(&self.nodes[node].cell, index);
+            if bound.relate_with(&self.loose(&child), Boundary::Closed) != Relation::In {
+                break;
+            }
+            node = self.child(node, index);
+        }
+
+        if node == 0 {
+            self.root_extent = self.root_extent.union(&bound);
+        }
+        self.nodes[node].items.push(handle);
+        self.entries.insert(handle, (bound, node, 0));
+        old
+    }
+
+    /// Remove a handle, returning its bound.
+    pub fn remove(&mut self, handle: &T) -> Option<B> {
+        match self.entries.remove(handle) {
+            Some((bound, node, _)) => {
+                let items = &mut self.nodes[node].items;
+                if let Some(i) = items.iter().position(|h| h == handle) {
+                    items.swap_remove(i);
+                }
+                Some(bound)
+            }
+            None => None,
+        }
+    }
+
+    /// Move a stored handle to a new bound. Returns `false` if the handle is
+    /// not in the tree.
+    pub fn update(&mut self, handle: T, bound: B) -> bool {
+        if !self.entries.contains_key(&handle) {
+            return false;
+        }
+        self.insert(handle, bound);
+        true
+    }
+
+    /// Find all handles whose bounds overlap `bound`.
+    pub fn query_bound(&self, bound: &B) -> Vec<T> {
+        self.query(|b| b.intersects(bound))
+    }
+
+    /// Find all handles whose bounds are within `radius` of `center`.
+    pub fn query_radius(&self, center: B::Point, radius: S) -> Vec<T> {
+        self.query(|b| b.distance2(center) <= radius * radius)
+    }
+
+    /// Find all handles whose bounds are hit by a ray, in front-to-back
+    /// order, together with the ray parameter at which the bound is entered.
+    /// Bounds containing the ray origin are entered at `0`.
+    pub fn query_ray(&self, ray: &Ray<S, B::Point, B::Vector>) -> Vec<(T, S)> {
+        let hit = |b: &B| b.ray_span_with(ray, Boundary::Closed).map(|span| span.enter);
+
+        let mut result = Vec::new();
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if hit(&self.loose_node(n)).is_none() {
+                continue;
+            }
+            for h in node.items.iter() {
+                if let Some(t) = hit(&self.entries[h].0) {
+                    result.push((*h, t));
+                }
+            }
+            self.push_children(n, &mut stack);
+        }
+
+        result.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+        result
+    }
+
+    fn query<F>(&self, filter: F) -> Vec<T>
+        where F: Fn(&B) -> bool
+    {
+        let mut result = Vec::new();
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            if !filter(&self.loose_node(n)) {
+                continue;
+            }
+            for h in self.nodes[n].items.iter() {
+                if filter(&self.entries[h].0) {
+                    result.push(*h);
+                }
+            }
+            self.push_children(n, &mut stack);
+        }
+        result
+    }
+
+    fn push_children(&self, n: usize, stack: &mut Vec<usize>) {
+        if let Some(first) = self.nodes[n].children {
+            for i in 0..(1 << axes::<S, B::Point>()) {
+                stack.push(first + i);
+            }
+        }
+    }
+
+    /// Collect the handles of a node and all of its descendants.
+    fn collect(&self, n: usize, result: &mut Vec<T>) {
+        let mut stack = vec![n];
+        while let Some(n) = stack.pop() {
+            result.extend(self.nodes[n].items.iter().cloned());
+            self.push_children(n, &mut stack);
+        }
+    }
+
+    fn loose_node(&self, n: usize) -> B {
+        if n == 0 {
+            // the root also keeps everything that does not fit elsewhere
+            self.root_extent
+        } else {
+            self.loose(&self.nodes[n].cell)
+        }
+    }
+
+    fn loose(&self, cell: &B) -> B {
+        let center = cell.center();
+        let half = cell.dim() * (self.looseness / (S::one() + S::one()));
+        B::new(center + half * -S::one(), center + half)
+    }
+
+    fn child(&mut self, n: usize, index: usize) -> usize {
+        let first = match self.nodes[n].children {
+            Some(first) => first,
+            None => {
+                let first = self.nodes.len();
+                for i in 0..(1 << axes::<S, B::Point>()) {
+                    let cell = child_cell(&self.nodes[n].cell, i);
+                    self.nodes.push(Node { cell: cell, children: None, items: Vec::new(), last_out: 0 });
+                }
+                self.nodes[n].children = Some(first);
+                first
+            }
+        };
+        first + index
+    }
+}
+
+impl<S, T> Octree<S, T>
+    where S: BaseFloat + 'static,
+          T: Copy + Eq + Hash
+{
+    /// Find all handles whose bounds overlap a sphere.
+    #[inline]
+    pub fn query_sphere(&self, sphere: &Sphere<S>) -> Vec<T> {
+        self.query_radius(sphere.center, sphere.radius)
+    }
+
+    /// Find all handles whose bounds are not outside of the frustum. Nodes
+    /// entirely inside the frustum are accepted without testing their
+    /// contents, and nodes outside of it are rejected as a whole. Nodes and
+    /// handles remember the plane that last rejected them, which is tested
+    /// first in the next call.
+    pub fn cull(&mut self, frustum: &Frustum<S>) -> Vec<T> {
+        let mut result = Vec::new();
+        let mut stack = vec![(0, PlaneMask::all())];
+        while let Some((n, mask)) = stack.pop() {
+            let bound = self.loose_node(n);
+            match frustum.contains_masked(bound, mask, &mut self.nodes[n].last_out) {
+                (Relation::Out, _) => {}
+                (Relation::In, _) => self.collect(n, &mut result),
+                (Relation::Cross, mask) => {
+                    let (nodes, entries) = (&self.nodes, &mut self.entries);
+                    for h in nodes[n].items.iter() {
+                        let entry = entries.get_mut(h).expect("stored handles have entries");
+                        if frustum.contains_masked(entry.0, mask, &mut entry.2).0 != Relation::Out {
+                            result.push(*h);
+                        }
+                    }
+                    if let Some(first) = nodes[n].children {
+                        for i in 0..8 {
+                            stack.push((first + i, mask));
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+/// The `index`-th child of a cell, where bit `i` of the index selects the
+/// upper half along axis `i`.
+fn child_cell<S, B>(cell: &B, index: usize) -> B
+    where S: BaseFloat,
+          B: GridBound<S> + Aabb<S, B::Vector, B::Point>
+{
+    let mid = cell.center();
+    let mut lo = cell.min();
+    let mut hi = mid;
+    for i in 0..axes::<S, B::Point>() {
+        if index & (1 << i) != 0 {
+            lo[i] = mid[i];
+            hi[i] = cell.max()[i];
+        }
+    }
+    B::new(lo, hi)
+}
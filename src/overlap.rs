@@ -0,0 +1,693 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Boolean overlap tests between pairs of bounds
+//!
+//! Most pairs are tested directly. Pairs of convex solids without a closed
+//! form test fall back to the Gilbert–Johnson–Keerthi algorithm on their
+//! support functions.
+
+use std::cmp::Ordering;
+
+use cgmath::{BaseNum, BaseFloat, EuclideanSpace, InnerSpace, Zero};
+use cgmath::{Point3, Vector2, Vector3};
+
+use {Aabb, Aabb2, Aabb3, Cylinder, Frustum, FrustumPoints, Line3, Obb3, Plane, Ray3, Sphere};
+use cylinder;
+use intersect::Intersect;
+
+/// Whether two objects share at least one point.
+///
+/// Solids are closed, so objects that only touch overlap. A `Plane` stands
+/// for its surface rather than one of its half-spaces, so a bound overlaps
+/// a plane when it touches or crosses it. Two-dimensional boxes only overlap
+/// other two-dimensional boxes.
+pub trait Overlap<Rhs> {
+    fn overlaps(&self, other: &Rhs) -> bool;
+}
+
+/// The shortest translation that moves an object out of another one, until
+/// the two only touch.
+pub trait Penetration<Rhs> {
+    /// The type of the translation.
+    type Output;
+
+    /// Find the translation to apply to `self`, or `None` if the objects do
+    /// not overlap.
+    fn penetration(&self, other: &Rhs) -> Option<Self::Output>;
+}
+
+macro_rules! mirror {
+    ($a:ident, $b:ident) => {
+        impl<S: BaseFloat + 'static> Overlap<$b<S>> for $a<S> {
+            #[inline]
+            fn overlaps(&self, other: &$b<S>) -> bool { other.overlaps(self) }
+        }
+    }
+}
+
+impl<S: BaseNum> Overlap<Aabb2<S>> for Aabb2<S> {
+    #[inline]
+    fn overlaps(&self, other: &Aabb2<S>) -> bool { self.intersects(other) }
+}
+
+impl<S: BaseNum> Overlap<Aabb3<S>> for Aabb3<S> {
+    #[inline]
+    fn overlaps(&self, other: &Aabb3<S>) -> bool { self.intersects(other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Sphere<S>> for Aabb3<S> {
+    fn overlaps(&self, other: &Sphere<S>) -> bool {
+        (self.closest_point(other.center) - other.center).magnitude2() <= other.radius * other.radius
+    }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Plane<S>> for Aabb3<S> {
+    fn overlaps(&self, other: &Plane<S>) -> bool { crosses(other, self) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Frustum<S>> for Aabb3<S> {
+    fn overlaps(&self, other: &Frustum<S>) -> bool { other.overlaps_aabb(self) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Obb3<S>> for Aabb3<S> {
+    fn overlaps(&self, other: &Obb3<S>) -> bool { gjk(self, other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Cylinder<S>> for Aabb3<S> {
+    fn overlaps(&self, other: &Cylinder<S>) -> bool { gjk(self, other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Line3<S>> for Aabb3<S> {
+    fn overlaps(&self, other: &Line3<S>) -> bool { (*other, *self).intersection().is_some() }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Ray3<S>> for Aabb3<S> {
+    fn overlaps(&self, other: &Ray3<S>) -> bool { (*other, *self).intersection().is_some() }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Sphere<S>> for Sphere<S> {
+    fn overlaps(&self, other: &Sphere<S>) -> bool {
+        let r = self.radius + other.radius;
+        (self.center - other.center).magnitude2() <= r * r
+    }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Plane<S>> for Sphere<S> {
+    fn overlaps(&self, other: &Plane<S>) -> bool { other.distance(self.center).abs() <= self.radius }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Frustum<S>> for Sphere<S> {
+    fn overlaps(&self, other: &Frustum<S>) -> bool { overlaps_frustum(other, self) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Obb3<S>> for Sphere<S> {
+    fn overlaps(&self, other: &Obb3<S>) -> bool { gjk(self, other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Cylinder<S>> for Sphere<S> {
+    fn overlaps(&self, other: &Cylinder<S>) -> bool {
+        let q = cylinder::closest_point(other, self.center);
+        (q - self.center).magnitude2() <= self.radius * self.radius
+    }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Line3<S>> for Sphere<S> {
+    fn overlaps(&self, other: &Line3<S>) -> bool { (*self, *other).intersection().is_some() }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Ray3<S>> for Sphere<S> {
+    fn overlaps(&self, other: &Ray3<S>) -> bool { (*self, *other).intersection().is_some() }
+}
+
+/// Planes overlap unless they are parallel and apart.
+impl<S: BaseFloat + 'static> Overlap<Plane<S>> for Plane<S> {
+    fn overlaps(&self, other: &Plane<S>) -> bool {
+        if self.n.cross(other.n).magnitude2() > S::zero() {
+            return true;
+        }
+        let dot = self.n.dot(other.n);
+        if dot == S::zero() {
+            return false;
+        }
+        // the other plane is `(dot * n) . x - other.d = 0` in terms of `n`
+        self.d * dot == other.d * self.n.magnitude2()
+    }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Frustum<S>> for Plane<S> {
+    fn overlaps(&self, other: &Frustum<S>) -> bool {
+        let (corners, edges) = match hull(other) {
+            Some(hull) => hull,
+            None => return true,
+        };
+        let dist = corners.iter().map(|c| self.distance(*c));
+        let (lo, hi) = dist.fold((S::infinity(), S::neg_infinity()), |(lo, hi), d| (lo.min(d), hi.max(d)));
+        let lo = if edges.iter().any(|e| self.n.dot(*e) < S::zero()) { S::neg_infinity() } else { lo };
+        let hi = if edges.iter().any(|e| self.n.dot(*e) > S::zero()) { S::infinity() } else { hi };
+        lo <= S::zero() && S::zero() <= hi
+    }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Obb3<S>> for Plane<S> {
+    fn overlaps(&self, other: &Obb3<S>) -> bool { crosses(self, other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Cylinder<S>> for Plane<S> {
+    fn overlaps(&self, other: &Cylinder<S>) -> bool { crosses(self, other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Line3<S>> for Plane<S> {
+    fn overlaps(&self, other: &Line3<S>) -> bool { (*self, *other).intersection().is_some() }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Ray3<S>> for Plane<S> {
+    fn overlaps(&self, other: &Ray3<S>) -> bool { (*self, *other).intersection().is_some() }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Frustum<S>> for Frustum<S> {
+    fn overlaps(&self, other: &Frustum<S>) -> bool { self.overlaps_frustum(other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Obb3<S>> for Frustum<S> {
+    fn overlaps(&self, other: &Obb3<S>) -> bool { self.overlaps_obb(other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Cylinder<S>> for Frustum<S> {
+    fn overlaps(&self, other: &Cylinder<S>) -> bool { overlaps_frustum(self, other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Line3<S>> for Frustum<S> {
+    fn overlaps(&self, other: &Line3<S>) -> bool { self.clip_line(other).is_some() }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Ray3<S>> for Frustum<S> {
+    fn overlaps(&self, other: &Ray3<S>) -> bool { self.clip_ray(other).is_some() }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Obb3<S>> for Obb3<S> {
+    fn overlaps(&self, other: &Obb3<S>) -> bool { gjk(self, other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Cylinder<S>> for Obb3<S> {
+    fn overlaps(&self, other: &Cylinder<S>) -> bool { gjk(self, other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Line3<S>> for Obb3<S> {
+    fn overlaps(&self, other: &Line3<S>) -> bool { gjk(self, other) }
+}
+
+/// Tested as a ray in the local frame of the box.
+impl<S: BaseFloat + 'static> Overlap<Ray3<S>> for Obb3<S> {
+    fn overlaps(&self, other: &Ray3<S>) -> bool {
+        let axes = self.axes();
+        let local = |v: Vector3<S>| Vector3::new(axes[0].dot(v), axes[1].dot(v), axes[2].dot(v));
+        let ray = Ray3::new(Point3::from_vec(local(other.origin - self.center)), local(other.direction));
+        let aabb = Aabb3::new(Point3::from_vec(-self.extents), Point3::from_vec(self.extents));
+        (ray, aabb).intersection().is_some()
+    }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Cylinder<S>> for Cylinder<S> {
+    fn overlaps(&self, other: &Cylinder<S>) -> bool { gjk(self, other) }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Line3<S>> for Cylinder<S> {
+    fn overlaps(&self, other: &Line3<S>) -> bool {
+        cylinder::clip_line(self, other.origin, other.dest - other.origin, S::zero(), S::one()).is_some()
+    }
+}
+
+impl<S: BaseFloat + 'static> Overlap<Ray3<S>> for Cylinder<S> {
+    fn overlaps(&self, other: &Ray3<S>) -> bool {
+        cylinder::clip_line(self, other.origin, other.direction, S::zero(), S::infinity()).is_some()
+    }
+}
+
+/// Segments overlap when they meet to within rounding errors.
+impl<S: BaseFloat + 'static> Overlap<Line3<S>> for Line3<S> {
+    fn overlaps(&self, other: &Line3<S>) -> bool {
+        meet(self.origin, self.dest - self.origin, S::one(),
+             other.origin, other.dest - other.origin, S::one())
+    }
+}
+
+/// A segment and a ray overlap when they meet to within rounding errors.
+impl<S: BaseFloat + 'static> Overlap<Ray3<S>> for Line3<S> {
+    fn overlaps(&self, other: &Ray3<S>) -> bool {
+        meet(self.origin, self.dest - self.origin, S::one(),
+             other.origin, other.direction, S::infinity())
+    }
+}
+
+/// Rays overlap when they meet to within rounding errors.
+impl<S: BaseFloat + 'static> Overlap<Ray3<S>> for Ray3<S> {
+    fn overlaps(&self, other: &Ray3<S>) -> bool {
+        meet(self.origin, self.direction, S::infinity(),
+             other.origin, other.direction, S::infinity())
+    }
+}
+
+mirror!(Sphere, Aabb3);
+mirror!(Plane, Aabb3);
+mirror!(Frustum, Aabb3);
+mirror!(Obb3, Aabb3);
+mirror!(Cylinder, Aabb3);
+mirror!(Line3, Aabb3);
+mirror!(Ray3, Aabb3);
+mirror!(Plane, Sphere);
+mirror!(Frustum, Sphere);
+mirror!(Obb3, Sphere);
+mirror!(Cylinder, Sphere);
+mirror!(Line3, Sphere);
+mirror!(Ray3, Sphere);
+mirror!(Frustum, Plane);
+mirror!(Obb3, Plane);
+mirror!(Cylinder, Plane);
+mirror!(Line3, Plane);
+mirror!(Ray3, Plane);
+mirror!(Obb3, Frustum);
+mirror!(Cylinder, Frustum);
+mirror!(Line3, Frustum);
+mirror!(Ray3, Frustum);
+mirror!(Cylinder, Obb3);
+mirror!(Line3, Obb3);
+mirror!(Ray3, Obb3);
+mirror!(Line3, Cylinder);
+mirror!(Ray3, Cylinder);
+mirror!(Ray3, Line3);
+
+impl<S: BaseFloat> Penetration<Aabb2<S>> for Aabb2<S> {
+    type Output = Vector2<S>;
+
+    fn penetration(&self, other: &Aabb2<S>) -> Option<Vector2<S>> {
+        let (i, depth) = match min_axis(&[self.min.x, self.min.y], &[self.max.x, self.max.y],
+                                        &[other.min.x, other.min.y], &[other.max.x, other.max.y]) {
+            Some(axis) => axis,
+            None => return None,
+        };
+        let mut v = Vector2::zero();
+        v[i] = depth;
+        Some(v)
+    }
+}
+
+impl<S: BaseFloat> Penetration<Aabb3<S>> for Aabb3<S> {
+    type Output = Vector3<S>;
+
+    fn penetration(&self, other: &Aabb3<S>) -> Option<Vector3<S>> {
+        let (i, depth) = match min_axis(&[self.min.x, self.min.y, self.min.z],
+                                        &[self.max.x, self.max.y, self.max.z],
+                                        &[other.min.x, other.min.y, other.min.z],
+                                        &[other.max.x, other.max.y, other.max.z]) {
+            Some(axis) => axis,
+            None => return None,
+        };
+        let mut v = Vector3::zero();
+        v[i] = depth;
+        Some(v)
+    }
+}
+
+impl<S: BaseFloat> Penetration<Sphere<S>> for Sphere<S> {
+    type Output = Vector3<S>;
+
+    fn penetration(&self, other: &Sphere<S>) -> Option<Vector3<S>> {
+        let v = self.center - other.center;
+        let dist = v.magnitude();
+        let depth = self.radius + other.radius - dist;
+        if depth < S::zero() {
+            return None;
+        }
+        let dir = if dist > S::zero() { v / dist } else { Vector3::unit_x() };
+        Some(dir * depth)
+    }
+}
+
+impl<S: BaseFloat> Penetration<Aabb3<S>> for Sphere<S> {
+    type Output = Vector3<S>;
+
+    fn penetration(&self, other: &Aabb3<S>) -> Option<Vector3<S>> {
+        if self.radius < S::zero() || self.radius.is_nan() {
+            return None;
+        }
+        let q = other.closest_point(self.center);
+        if q != self.center {
+            let v = self.center - q;
+            let dist = v.magnitude();
+            return if dist <= self.radius { Some(v * ((self.radius - dist) / dist)) } else { None };
+        }
+        // the center is inside the box, push it out through the nearest face
+        let (c, r) = (self.center, self.radius);
+        let (i, depth) = match min_axis(&[c.x - r, c.y - r, c.z - r], &[c.x + r, c.y + r, c.z + r],
+                                        &[other.min.x, other.min.y, other.min.z],
+                                        &[other.max.x, other.max.y, other.max.z]) {
+            Some(axis) => axis,
+            None => return None,
+        };
+        let mut v = Vector3::zero();
+        v[i] = depth;
+        Some(v)
+    }
+}
+
+impl<S: BaseFloat> Penetration<Sphere<S>> for Aabb3<S> {
+    type Output = Vector3<S>;
+
+    fn penetration(&self, other: &Sphere<S>) -> Option<Vector3<S>> {
+        other.penetration(self).map(|v| -v)
+    }
+}
+
+/// Moves the sphere off of the plane to the side its center is on, or the
+/// inside if the center lies on the plane.
+impl<S: BaseFloat> Penetration<Plane<S>> for Sphere<S> {
+    type Output = Vector3<S>;
+
+    fn penetration(&self, other: &Plane<S>) -> Option<Vector3<S>> {
+        let dist = other.distance(self.center);
+        if dist.abs() > self.radius {
+            None
+        } else if dist >= S::zero() {
+            Some(other.n * (self.radius - dist))
+        } else {
+            Some(other.n * (-self.radius - dist))
+        }
+    }
+}
+
+impl<S: BaseFloat> Penetration<Sphere<S>> for Plane<S> {
+    type Output = Vector3<S>;
+
+    fn penetration(&self, other: &Sphere<S>) -> Option<Vector3<S>> {
+        other.penetration(self).map(|v| -v)
+    }
+}
+
+/// Find the axis along which the box `[a_min, a_max]` has to move the least
+/// to leave the box `[b_min, b_max]`, and the signed distance to move it.
+fn min_axis<S: BaseFloat>(a_min: &[S], a_max: &[S], b_min: &[S], b_max: &[S]) -> Option<(usize, S)> {
+    let mut best: Option<(usize, S)> = None;
+    for i in 0..a_min.len() {
+        let up = b_max[i] - a_min[i];
+        let down = a_max[i] - b_min[i];
+        if up < S::zero() || down < S::zero() {
+            return None;
+        }
+        let depth = if up < down { up } else { -down };
+        if best.map_or(true, |(_, d)| depth.abs() < d.abs()) {
+            best = Some((i, depth));
+        }
+    }
+    best
+}
+
+/// Whether the parametric lines `o1 + s * d1`, `s` in `[0, max1]`, and
+/// `o2 + t * d2`, `t` in `[0, max2]`, come within rounding errors of each
+/// other.
+///
+/// See _Real-Time Collision Detection_, p. 148 - 151
+fn meet<S: BaseFloat>(o1: Point3<S>, d1: Vector3<S>, max1: S,
+                      o2: Point3<S>, d2: Vector3<S>, max2: S) -> bool {
+    let r = o1 - o2;
+    let a = d1.dot(d1);
+    let e = d2.dot(d2);
+    let f = d2.dot(r);
+    let clamp = |x: S, max: S| x.max(S::zero()).min(max);
+
+    let (s, t) = if a == S::zero() && e == S::zero() {
+        (S::zero(), S::zero())
+    } else if a == S::zero() {
+        (S::zero(), clamp(f / e, max2))
+    } else {
+        let c = d1.dot(r);
+        if e == S::zero() {
+            (clamp(-c / a, max1), S::zero())
+        } else {
+            let b = d1.dot(d2);
+            let denom = a * e - b * b;
+            let s = if denom != S::zero() { clamp((b * f - c * e) / denom, max1) } else { S::zero() };
+            let t = (b * s + f) / e;
+            if t < S::zero() {
+                (clamp(-c / a, max1), S::zero())
+            } else if t > max2 {
+                (clamp((b * max2 - c) / a, max1), max2)
+            } else {
+                (s, t)
+            }
+        }
+    };
+    let p1 = if s == S::zero() { o1 } else { o1 + d1 * s };
+    let p2 = if t == S::zero() { o2 } else { o2 + d2 * t };
+    (p1 - p2).magnitude2() <= S::epsilon() * (S::one() + o1.to_vec().magnitude2().max(o2.to_vec().magnitude2()))
+}
+
+/// Whether a convex solid touches or crosses a plane.
+fn crosses<S: BaseFloat, T: Support<S>>(plane: &Plane<S>, solid: &T) -> bool {
+    let hi = plane.distance(solid.support(plane.n));
+    let lo = plane.distance(solid.support(-plane.n));
+    lo <= S::zero() && S::zero() <= hi
+}
+
+/// Corner points together with the directions of unbounded edges.
+type Hull<S> = (Vec<Point3<S>>, Vec<Vector3<S>>);
+
+/// The corners of a frustum and, for an infinite frustum, the directions of
+/// its side edges, which only has corners on the near plane.
+fn hull<S: BaseFloat + 'static>(frustum: &Frustum<S>) -> Option<Hull<S>> {
+    if let Some(points) = frustum.to_points() {
+        return Some((points.to_corners().to_vec(), Vec::new()));
+    }
+    let f = frustum;
+    let sides = [(f.top, f.left), (f.top, f.right), (f.bottom, f.left), (f.bottom, f.right)];
+    let mut corners = Vec::with_capacity(4);
+    let mut edges = Vec::with_capacity(4);
+    for &(a, b) in sides.iter() {
+        match (f.near, a, b).intersection() {
+            Some(c) => corners.push(c),
+            None => return None,
+        }
+        let e = a.n.cross(b.n);
+        edges.push(if e.dot(f.near.n) < S::zero() { -e } else { e });
+    }
+    Some((corners, edges))
+}
+
+/// Whether a bounded convex solid overlaps a frustum. An infinite frustum is
+/// closed by a far plane beyond the solid first.
+fn overlaps_frustum<S: BaseFloat + 'static, T: Support<S>>(frustum: &Frustum<S>, solid: &T) -> bool {
+    let planes = frustum.planes();
+    if planes.iter().any(|p| p.distance(solid.support(p.n)) < S::zero()) {
+        return false;
+    }
+    let mut bounded = *frustum;
+    if bounded.far.is_none() {
+        let n = frustum.near.n;
+        let reach = n.dot(solid.support(n).to_vec());
+        bounded.far = Some(Plane::new(-n, -(reach + reach.abs() + S::one())));
+    }
+    match bounded.to_points() {
+        Some(points) => gjk(&points, solid),
+        None => true,
+    }
+}
+
+/// A convex solid described by its support function.
+trait Support<S: BaseFloat> {
+    /// A point of the solid farthest along `dir`.
+    fn support(&self, dir: Vector3<S>) -> Point3<S>;
+}
+
+impl<S: BaseFloat> Support<S> for Aabb3<S> {
+    fn support(&self, dir: Vector3<S>) -> Point3<S> {
+        let pick = |d: S, min: S, max: S| if d < S::zero() { min } else { max };
+        Point3::new(pick(dir.x, self.min.x, self.max.x),
+                    pick(dir.y, self.min.y, self.max.y),
+                    pick(dir.z, self.min.z, self.max.z))
+    }
+}
+
+impl<S: BaseFloat> Support<S> for Sphere<S> {
+    fn support(&self, dir: Vector3<S>) -> Point3<S> {
+        if dir == Vector3::zero() {
+            self.center
+        } else {
+            self.center + dir.normalize() * self.radius
+        }
+    }
+}
+
+impl<S: BaseFloat> Support<S> for Obb3<S> {
+    fn support(&self, dir: Vector3<S>) -> Point3<S> {
+        let axes = self.axes();
+        let extents = [self.extents.x, self.extents.y, self.extents.z];
+        axes.iter().zip(extents.iter()).fold(self.center, |p, (a, e)| {
+            if a.dot(dir) < S::zero() { p + *a * -*e } else { p + *a * *e }
+        })
+    }
+}
+
+impl<S: BaseFloat> Support<S> for Cylinder<S> {
+    fn support(&self, dir: Vector3<S>) -> Point3<S> { cylinder::support(self, dir) }
+}
+
+impl<S: BaseFloat> Support<S> for Line3<S> {
+    fn support(&self, dir: Vector3<S>) -> Point3<S> {
+        if (self.dest - self.origin).dot(dir) < S::zero() { self.origin } else { self.dest }
+    }
+}
+
+impl<S: BaseFloat> Support<S> for FrustumPoints<S> {
+    fn support(&self, dir: Vector3<S>) -> Point3<S> {
+        let corners = self.to_corners();
+        corners.iter().skip(1).fold(corners[0], |best, c| {
+            if c.to_vec().dot(dir) > best.to_vec().dot(dir) { *c } else { best }
+        })
+    }
+}
+
+/// Whether two convex solids overlap, by the Gilbert–Johnson–Keerthi
+/// algorithm. Gives up and reports an overlap if it does not converge.
+///
+/// See _Real-Time Collision Detection_, p. 399 - 404
+fn gjk<S: BaseFloat, A: Support<S>, B: Support<S>>(a: &A, b: &B) -> bool {
+    let support = |d: Vector3<S>| a.support(d) - b.support(-d);
+    let mut simplex = vec![support(Vector3::unit_x())];
+    for _ in 0..64 {
+        let v = closest_on_simplex(&mut simplex);
+        let scale = simplex.iter().fold(S::one(), |m, p| m.max(p.magnitude2()));
+        let tolerance = S::epsilon() * S::from(100).unwrap() * scale;
+        if v.magnitude2() <= tolerance {
+            return true;
+        }
+        let w = support(-v);
+        if v.dot(w) > S::zero() {
+            // found a separating axis
+            return false;
+        }
+        if v.magnitude2() - v.dot(w) <= tolerance {
+            // no more progress towards the origin, which is touched
+            return true;
+        }
+        simplex.push(w);
+    }
+    true
+}
+
+/// Find the point of a simplex nearest to the origin, and reduce the simplex
+/// to the smallest face containing it.
+fn closest_on_simplex<S: BaseFloat>(simplex: &mut Vec<Vector3<S>>) -> Vector3<S> {
+    match simplex.len() {
+        1 => simplex[0],
+        2 => {
+            let (p, keep) = closest_on_segment(simplex[0], simplex[1]);
+            retain(simplex, &keep);
+            p
+        }
+        3 => {
+            let (p, keep) = closest_on_triangle(simplex[0], simplex[1], simplex[2]);
+            retain(simplex, &keep);
+            p
+        }
+        _ => {
+            let (a, b, c, d) = (simplex[0], simplex[1], simplex[2], simplex[3]);
+            let faces = [(a, b, c, d, [0, 1, 2]), (a, c, d, b, [0, 2, 3]),
+                         (a, d, b, c, [0, 3, 1]), (b, d, c, a, [1, 3, 2])];
+            let mut best: Option<(Vector3<S>, Vec<usize>)> = None;
+            for &(p, q, r, opposite, ids) in faces.iter() {
+                // skip faces that the origin is on the same side of as the
+                // opposite corner
+                let n = (q - p).cross(r - p);
+                if n.dot(-p) * n.dot(opposite - p) > S::zero() {
+                    continue;
+                }
+                let (x, keep) = closest_on_triangle(p, q, r);
+                if best.as_ref().map_or(true, |b| x.magnitude2() < b.0.magnitude2()) {
+                    best = Some((x, keep.iter().map(|&k| ids[k]).collect()));
+                }
+            }
+            match best {
+                Some((x, keep)) => {
+                    retain(simplex, &keep);
+                    x
+                }
+                // the origin is inside of the tetrahedron
+                None => Vector3::zero(),
+            }
+        }
+    }
+}
+
+/// Keep the corners of a simplex with the given indices.
+fn retain<S: BaseFloat>(simplex: &mut Vec<Vector3<S>>, keep: &[usize]) {
+    *simplex = keep.iter().map(|&i| simplex[i]).collect();
+}
+
+/// The point of the segment `ab` nearest to the origin, and the corners of
+/// the segment needed to describe it.
+fn closest_on_segment<S: BaseFloat>(a: Vector3<S>, b: Vector3<S>) -> (Vector3<S>, Vec<usize>) {
+    let ab = b - a;
+    let t = -a.dot(ab);
+    if t <= S::zero() {
+        (a, vec![0])
+    } else if t >= ab.magnitude2() {
+        (b, vec![1])
+    } else {
+        (a + ab * (t / ab.magnitude2()), vec![0, 1])
+    }
+}
+
+/// The point of the triangle `abc` nearest to the origin, and the corners of
+/// the triangle needed to describe it.
+///
+/// See _Real-Time Collision Detection_, p. 136 - 142
+fn closest_on_triangle<S: BaseFloat>(a: Vector3<S>, b: Vector3<S>, c: Vector3<S>) -> (Vector3<S>, Vec<usize>) {
+    let (ab, ac) = (b - a, c - a);
+    let (d1, d2) = (ab.dot(-a), ac.dot(-a));
+    if d1 <= S::zero() && d2 <= S::zero() {
+        return (a, vec![0]);
+    }
+    let (d3, d4) = (ab.dot(-b), ac.dot(-b));
+    if d3 >= S::zero() && d4 <= d3 {
+        return (b, vec![1]);
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= S::zero() && d1 >= S::zero() && d3 <= S::zero() {
+        return (a + ab * (d1 / (d1 - d3)), vec![0, 1]);
+    }
+    let (d5, d6) = (ab.dot(-c), ac.dot(-c));
+    if d6 >= S::zero() && d5 <= d6 {
+        return (c, vec![2]);
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= S::zero() && d2 >= S::zero() && d6 <= S::zero() {
+        return (a + ac * (d2 / (d2 - d6)), vec![0, 2]);
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= S::zero() && d4 - d3 >= S::zero() && d5 - d6 >= S::zero() {
+        return (b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6))), vec![1, 2]);
+    }
+    let sum = va + vb + vc;
+    if sum == S::zero() {
+        // a degenerate triangle, use the nearest of its edges
+        let edges = [closest_on_segment(a, b), closest_on_segment(a, c), closest_on_segment(b, c)];
+        let ids = [[0, 1], [0, 2], [1, 2]];
+        let (i, &(p, ref keep)) = edges.iter().enumerate()
+            .min_by(|x, y| (x.1).0.magnitude2().partial_cmp(&(y.1).0.magnitude2()).unwrap_or(Ordering::Equal))
+            .unwrap();
+        return (p, keep.iter().map(|&k| ids[i][k]).collect());
+    }
+    (a + ab * (vb / sum) + ac * (vc / sum), vec![0, 1, 2])
+}
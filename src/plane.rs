@@ -18,9 +18,12 @@ use std::fmt;
 use cgmath::{ApproxEq, BaseFloat};
 use cgmath::{Point3};
 use cgmath::{Vector3, Vector4};
+use cgmath::{Matrix, Matrix4, SquareMatrix};
 use cgmath::{EuclideanSpace, InnerSpace};
 use cgmath::{Zero};
 
+use Ray3;
+
 
 /// A 3-dimensional plane formed from the equation: `A*x + B*y + C*z - D = 0`.
 ///
@@ -57,12 +60,20 @@ impl<S: BaseFloat> Plane<S> {
     /// - `a`: the `x` component of the normal
     /// - `b`: the `y` component of the normal
     /// - `c`: the `z` component of the normal
-    /// - `d`: the plane's distance value
+    /// - `d`: the plane's distance value, in the `A*x + B*y + C*z - D = 0`
+    ///   form
     pub fn from_abcd(a: S, b: S, c: S, d: S) -> Plane<S> {
         Plane { n: Vector3::new(a, b, c), d: d }
     }
 
+    /// Construct a plane from the coefficients of the alternative
+    /// `A*x + B*y + C*z + D = 0` form.
+    pub fn from_abcd_alt(a: S, b: S, c: S, d: S) -> Plane<S> {
+        Plane { n: Vector3::new(a, b, c), d: -d }
+    }
+
     /// Construct a plane from the components of a four-dimensional vector
+    /// `(A, B, C, D)` of the `A*x + B*y + C*z - D = 0` form.
     pub fn from_vector4(v: Vector4<S>) -> Plane<S> {
         Plane { n: Vector3::new(v.x, v.y, v.z), d: v.w }
     }
@@ -73,6 +84,20 @@ impl<S: BaseFloat> Plane<S> {
         Plane { n: Vector3::new(v.x, v.y, v.z), d: -v.w }
     }
 
+    /// The coefficients `(A, B, C, D)` of the `A*x + B*y + C*z - D = 0` form.
+    #[inline]
+    pub fn to_vector4(&self) -> Vector4<S> {
+        Vector4::new(self.n.x, self.n.y, self.n.z, self.d)
+    }
+
+    /// The coefficients `(A, B, C, D)` of the alternative
+    /// `A*x + B*y + C*z + D = 0` form, as used by homogeneous coordinates:
+    /// the plane contains the points `p` with `v.dot(p.to_homogeneous()) == 0`.
+    #[inline]
+    pub fn to_vector4_alt(&self) -> Vector4<S> {
+        Vector4::new(self.n.x, self.n.y, self.n.z, -self.d)
+    }
+
     /// Constructs a plane that passes through the the three points `a`, `b` and `c`
     pub fn from_points(a: Point3<S>, b: Point3<S>, c: Point3<S>) -> Option<Plane<S>> {
         // create two vectors that run parallel to the plane
@@ -86,7 +111,7 @@ impl<S: BaseFloat> Plane<S> {
         else {
             // compute the normal and the distance to the plane
             let n = n.normalize();
-            let d = -a.dot(n);
+            let d = a.dot(n);
 
             Some(Plane::new(n, d))
         }
@@ -106,6 +131,76 @@ impl<S: BaseFloat> Plane<S> {
             Some(Plane::new(self.n * denom, self.d*denom))
         }
     }
+
+    /// The signed distance from the plane to a point, positive on the side
+    /// the normal points to. The distance is scaled by the length of the
+    /// normal if it is not a unit vector.
+    #[inline]
+    pub fn distance(&self, p: Point3<S>) -> S {
+        self.n.dot(p.to_vec()) - self.d
+    }
+
+    /// The point on the plane closest to `p`.
+    pub fn project_point(&self, p: Point3<S>) -> Point3<S> {
+        p + self.n * -(self.distance(p) / self.n.magnitude2())
+    }
+
+    /// Mirror a point across the plane.
+    pub fn reflect_point(&self, p: Point3<S>) -> Point3<S> {
+        let two = S::one() + S::one();
+        p + self.n * -(two * self.distance(p) / self.n.magnitude2())
+    }
+
+    /// Mirror a direction across the plane.
+    pub fn reflect_vector(&self, v: Vector3<S>) -> Vector3<S> {
+        let two = S::one() + S::one();
+        v - self.n * (two * self.n.dot(v) / self.n.magnitude2())
+    }
+
+    /// Mirror a ray across the plane.
+    pub fn reflect_ray(&self, ray: &Ray3<S>) -> Ray3<S> {
+        Ray3::new(self.reflect_point(ray.origin), self.reflect_vector(ray.direction))
+    }
+
+    /// The same plane, facing the other way.
+    #[inline]
+    pub fn flip(&self) -> Plane<S> {
+        Plane::new(-self.n, -self.d)
+    }
+
+    /// Transform the plane by a matrix, returning the normalized plane
+    /// containing the transformed points of this one. Returns `None` if the
+    /// matrix is not invertible.
+    pub fn transform(&self, m: Matrix4<S>) -> Option<Plane<S>> {
+        let inv = match m.invert() { Some(inv) => inv, None => return None };
+        let v = inv.transpose() * self.to_vector4_alt();
+        Plane::from_vector4_alt(v).normalize()
+    }
+}
+
+/// Clip the line `origin + t * dir` to the intersection of the positive
+/// sides of a set of planes, starting from the range `[t0, t1]`.
+///
+/// See _Real-Time Collision Detection_, p. 198 - 199
+pub fn clip_line<S: BaseFloat>(planes: &[Plane<S>], origin: Point3<S>, dir: Vector3<S>,
+                               mut t0: S, mut t1: S) -> Option<(S, S)> {
+    for p in planes.iter() {
+        let dist = p.distance(origin);
+        let denom = p.n.dot(dir);
+        if denom == S::zero() {
+            if dist < S::zero() {
+                return None;
+            }
+        } else {
+            let t = -dist / denom;
+            if denom > S::zero() {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    if t0 <= t1 { Some((t0, t1)) } else { None }
 }
 
 impl<S> ApproxEq for Plane<S>
@@ -0,0 +1,126 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Convex polygons and polyhedra in three dimensions
+
+use cgmath::{BaseFloat, EuclideanSpace, InnerSpace};
+use cgmath::{Point3, Vector3, Zero};
+
+use {Frustum, Plane, Ray3};
+use plane;
+use span::{RaySpan, Span};
+
+/// A convex polygon, with its points in counter-clockwise order when seen
+/// from the side its normal points to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvexPolygon<S> {
+    pub points: Vec<Point3<S>>,
+}
+
+impl<S: BaseFloat> ConvexPolygon<S> {
+    /// Construct a polygon from its points.
+    pub fn new(points: Vec<Point3<S>>) -> ConvexPolygon<S> {
+        ConvexPolygon { points: points }
+    }
+
+    /// The normal of the polygon, not normalized, computed from all of its
+    /// points to tolerate slightly non-planar input.
+    pub fn normal(&self) -> Vector3<S> {
+        let mut n = Vector3::zero();
+        if let Some(&first) = self.points.first() {
+            for w in self.points[1..].windows(2) {
+                n = n + (w[0] - first).cross(w[1] - first);
+            }
+        }
+        n
+    }
+
+    /// The plane of the polygon, facing the side the points are ordered
+    /// counter-clockwise from. Returns `None` if the polygon is degenerate.
+    pub fn plane(&self) -> Option<Plane<S>> {
+        let n = self.normal();
+        if ulps_eq!(n, &Vector3::zero()) {
+            return None;
+        }
+        Some(Plane::from_point_normal(self.centroid(), n.normalize()))
+    }
+
+    /// The average of the points of the polygon.
+    pub fn centroid(&self) -> Point3<S> {
+        Point3::centroid(&self.points)
+    }
+
+    /// Keep the part of the polygon on the positive side of a plane.
+    /// Returns `None` if nothing of it is left.
+    ///
+    /// See _Real-Time Collision Detection_, p. 367 - 370
+    pub fn clip_plane(&self, plane: &Plane<S>) -> Option<ConvexPolygon<S>> {
+        let n = self.points.len();
+        let mut points = Vec::with_capacity(n + 1);
+        for i in 0..n {
+            let (a, b) = (self.points[i], self.points[(i + 1) % n]);
+            let (da, db) = (plane.distance(a), plane.distance(b));
+            if da >= S::zero() {
+                points.push(a);
+            }
+            if (da > S::zero() && db < S::zero()) || (da < S::zero() && db > S::zero()) {
+                points.push(a + (b - a) * (da / (da - db)));
+            }
+        }
+        if points.len() < 3 { None } else { Some(ConvexPolygon::new(points)) }
+    }
+
+    /// Keep the part of the polygon inside a frustum.
+    pub fn clip_frustum(&self, frustum: &Frustum<S>) -> Option<ConvexPolygon<S>>
+        where S: 'static
+    {
+        self.clip_planes(&frustum.planes())
+    }
+
+    /// Keep the part of the polygon on the positive side of all planes.
+    pub fn clip_planes(&self, planes: &[Plane<S>]) -> Option<ConvexPolygon<S>> {
+        let mut polygon = self.clone();
+        for plane in planes.iter() {
+            polygon = match polygon.clip_plane(plane) { Some(p) => p, None => return None };
+        }
+        Some(polygon)
+    }
+}
+
+/// A convex polyhedron, as the intersection of the positive sides of its
+/// face planes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvexPolyhedron<S: BaseFloat> {
+    pub planes: Vec<Plane<S>>,
+}
+
+impl<S: BaseFloat> ConvexPolyhedron<S> {
+    /// Construct a polyhedron from its face planes.
+    pub fn new(planes: Vec<Plane<S>>) -> ConvexPolyhedron<S> {
+        ConvexPolyhedron { planes: planes }
+    }
+
+    /// Whether a point lies inside of the polyhedron or on its boundary.
+    pub fn contains(&self, p: Point3<S>) -> bool {
+        self.planes.iter().all(|plane| plane.distance(p) >= S::zero())
+    }
+}
+
+impl<S: BaseFloat> RaySpan<S, Ray3<S>> for ConvexPolyhedron<S> {
+    fn ray_span(&self, ray: &Ray3<S>) -> Option<Span<S>> {
+        plane::clip_line(&self.planes, ray.origin, ray.direction, S::zero(), S::infinity())
+            .map(|(t0, t1)| Span::new(t0, t1))
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Portal-based visibility culling
+//!
+//! The world is divided into cells connected by portals, convex polygons
+//! like doors and windows. Looking through a portal narrows the view to the
+//! part of the portal that is visible, so only cells seen through a chain of
+//! portals need to be drawn.
+
+use std::cmp::max;
+
+use cgmath::{BaseFloat, InnerSpace, Zero};
+use cgmath::{Point3, Vector3};
+
+use {ConvexPolygon, Frustum, Plane};
+use bound::{Bound, Relation};
+
+/// A convex view volume from an eye point, bounded by any number of planes
+/// facing inwards.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortalFrustum<S: BaseFloat> {
+    pub eye: Point3<S>,
+    /// The planes through the eye and along the sides of the view, followed
+    /// by the plane cutting off everything in front of the last portal.
+    pub planes: Vec<Plane<S>>,
+    /// The far plane of the original view, kept by all narrowed views.
+    pub far: Option<Plane<S>>,
+}
+
+impl<S: BaseFloat + 'static> PortalFrustum<S> {
+    /// Construct the view of a frustum seen from `eye`.
+    pub fn from_frustum(eye: Point3<S>, frustum: &Frustum<S>) -> PortalFrustum<S> {
+        PortalFrustum {
+            eye: eye,
+            planes: vec![frustum.left, frustum.right, frustum.bottom, frustum.top, frustum.near],
+            far: frustum.far,
+        }
+    }
+
+    /// Find the spatial relation of a bound inside the view.
+    pub fn contains<B: Bound<S>>(&self, bound: B) -> Relation {
+        let mut relation = Relation::In;
+        for plane in self.planes.iter().chain(self.far.iter()) {
+            relation = max(relation, bound.relate_plane(*plane));
+            if relation == Relation::Out {
+                break;
+            }
+        }
+        relation
+    }
+
+    /// Keep the part of a polygon inside the view.
+    pub fn clip(&self, polygon: &ConvexPolygon<S>) -> Option<ConvexPolygon<S>> {
+        let clipped = match polygon.clip_planes(&self.planes) { Some(p) => p, None => return None };
+        match self.far {
+            Some(far) => clipped.clip_plane(&far),
+            None => Some(clipped),
+        }
+    }
+
+    /// Narrow the view to what can be seen through a portal. Returns `None`
+    /// if the portal is not visible. If the eye lies in the plane of the
+    /// portal, the view is kept as it is.
+    pub fn through(&self, portal: &ConvexPolygon<S>) -> Option<PortalFrustum<S>> {
+        let clipped = match self.clip(portal) { Some(p) => p, None => return None };
+        let plane = match clipped.plane() { Some(p) => p, None => return None };
+
+        // face the portal plane away from the eye
+        let dist = plane.distance(self.eye);
+        if ulps_eq!(dist, &S::zero()) {
+            return Some(self.clone());
+        }
+        let plane = if dist > S::zero() { plane.flip() } else { plane };
+
+        let center = clipped.centroid();
+        let n = clipped.points.len();
+        let mut planes = Vec::with_capacity(n + 1);
+        for i in 0..n {
+            let (a, b) = (clipped.points[i], clipped.points[(i + 1) % n]);
+            let normal = (a - self.eye).cross(b - self.eye);
+            if ulps_eq!(normal, &Vector3::zero()) {
+                continue;
+            }
+            let normal = normal.normalize();
+            let normal = if normal.dot(center - self.eye) < S::zero() { -normal } else { normal };
+            planes.push(Plane::from_point_normal(self.eye, normal));
+        }
+        planes.push(plane);
+
+        Some(PortalFrustum {
+            eye: self.eye,
+            planes: planes,
+            far: self.far,
+        })
+    }
+}
+
+/// A portal connecting two cells.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Portal<S> {
+    pub cells: (usize, usize),
+    pub polygon: ConvexPolygon<S>,
+}
+
+/// A graph of cells connected by portals.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortalGraph<S> {
+    portals: Vec<Portal<S>>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl<S: BaseFloat + 'static> PortalGraph<S> {
+    /// Create an empty graph.
+    pub fn new() -> PortalGraph<S> {
+        PortalGraph {
+            portals: Vec::new(),
+            adjacency: Vec::new(),
+        }
+    }
+
+    /// Add a cell, returning its index.
+    pub fn add_cell(&mut self) -> usize {
+        self.adjacency.push(Vec::new());
+        self.adjacency.len() - 1
+    }
+
+    /// Connect two cells with a portal, returning its index. Portals can be
+    /// looked through from either side.
+    pub fn add_portal(&mut self, a: usize, b: usize, polygon: ConvexPolygon<S>) -> usize {
+        let index = self.portals.len();
+        self.portals.push(Portal { cells: (a, b), polygon: polygon });
+        self.adjacency[a].push(index);
+        if a != b {
+            self.adjacency[b].push(index);
+        }
+        index
+    }
+
+    /// The number of cells in the graph.
+    #[inline]
+    pub fn cell_count(&self) -> usize { self.adjacency.len() }
+
+    /// The portals of the graph.
+    #[inline]
+    pub fn portals(&self) -> &[Portal<S>] { &self.portals }
+
+    /// Find the cells visible from `cell` in `view`, together with the view
+    /// narrowed to what can be seen of each cell, starting with `cell`
+    /// itself. A cell seen through several chains of portals is returned
+    /// once for each of them.
+    pub fn visible(&self, cell: usize, view: &PortalFrustum<S>) -> Vec<(usize, PortalFrustum<S>)> {
+        let mut result = Vec::new();
+        let mut on_path = vec![false; self.adjacency.len()];
+        self.visit(cell, view.clone(), &mut on_path, &mut result);
+        result
+    }
+
+    fn visit(&self, cell: usize, view: PortalFrustum<S>, on_path: &mut Vec<bool>,
+             result: &mut Vec<(usize, PortalFrustum<S>)>) {
+        on_path[cell] = true;
+        result.push((cell, view.clone()));
+        for &p in self.adjacency[cell].iter() {
+            let portal = &self.portals[p];
+            let next = if portal.cells.0 == cell { portal.cells.1 } else { portal.cells.0 };
+            if on_path[next] {
+                continue;
+            }
+            if let Some(narrowed) = view.through(&portal.polygon) {
+                self.visit(next, narrowed, on_path, result);
+            }
+        }
+        on_path[cell] = false;
+    }
+}
+
+impl<S: BaseFloat + 'static> Default for PortalGraph<S> {
+    fn default() -> PortalGraph<S> { PortalGraph::new() }
+}
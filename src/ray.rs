@@ -14,9 +14,11 @@
 // limitations under the License.
 
 use std::marker::PhantomData;
-use cgmath::BaseNum;
+use cgmath::{BaseNum, BaseFloat};
 use cgmath::{EuclideanSpace, Point2, Point3};
-use cgmath::{VectorSpace, Vector2, Vector3};
+use cgmath::{VectorSpace, InnerSpace, Vector2, Vector3};
+
+use {Line, Line3};
 
 /// A generic ray starting at `origin` and extending infinitely in
 /// `direction`.
@@ -42,5 +44,110 @@ impl<S, V, P> Ray<S, P, V>
     }
 }
 
+impl<S, V, P> Ray<S, P, V>
+    where S: BaseFloat,
+          V: InnerSpace<Scalar=S>,
+          P: EuclideanSpace<Scalar=S, Diff=V>
+{
+    /// The point at parameter `t` along the ray.
+    #[inline]
+    pub fn point_at(&self, t: S) -> P { self.origin + self.direction * t }
+
+    /// The non-negative parameter of the point on the ray closest to `p`.
+    pub fn closest_param(&self, p: P) -> S {
+        let len2 = self.direction.magnitude2();
+        if len2 == S::zero() {
+            return S::zero();
+        }
+        ((p - self.origin).dot(self.direction) / len2).max(S::zero())
+    }
+
+    /// The point on the ray closest to `p`.
+    #[inline]
+    pub fn closest_point(&self, p: P) -> P { self.point_at(self.closest_param(p)) }
+
+    /// The segment covering the ray's parameter range `[t_min, t_max]`.
+    #[inline]
+    pub fn to_line(&self, t_min: S, t_max: S) -> Line<S, V, P> {
+        Line::new(self.point_at(t_min), self.point_at(t_max))
+    }
+}
+
+impl<S: BaseFloat> Ray3<S> {
+    /// Find the parameters of the closest points between this ray and a
+    /// segment, as `(ray parameter, segment parameter)`. The segment
+    /// parameter is in `[0, 1]`.
+    ///
+    /// See _Real-Time Collision Detection_, p. 148 - 151
+    pub fn closest_approach(&self, line: &Line3<S>) -> (S, S) {
+        let d1 = self.direction;
+        let d2 = line.dest - line.origin;
+        let r = self.origin - line.origin;
+        let a = d1.dot(d1);
+        let e = d2.dot(d2);
+        let f = d2.dot(r);
+        let clamp_t = |t: S| t.max(S::zero()).min(S::one());
+
+        if a == S::zero() && e == S::zero() {
+            return (S::zero(), S::zero());
+        }
+        if a == S::zero() {
+            return (S::zero(), clamp_t(f / e));
+        }
+
+        let c = d1.dot(r);
+        if e == S::zero() {
+            return ((-c / a).max(S::zero()), S::zero());
+        }
+
+        let b = d1.dot(d2);
+        let denom = a * e - b * b;
+        let s = if denom != S::zero() {
+            ((b * f - c * e) / denom).max(S::zero())
+        } else {
+            // parallel, pick the ray origin
+            S::zero()
+        };
+
+        let t = (b * s + f) / e;
+        if t < S::zero() {
+            ((-c / a).max(S::zero()), S::zero())
+        } else if t > S::one() {
+            (((b - c) / a).max(S::zero()), S::one())
+        } else {
+            (s, t)
+        }
+    }
+
+    /// Find the intersection of the ray with the triangle `abc`, using the
+    /// Möller–Trumbore algorithm. Both windings of the triangle are hit.
+    pub fn intersect_triangle(&self, a: Point3<S>, b: Point3<S>, c: Point3<S>) -> Option<Point3<S>> {
+        let e1 = b - a;
+        let e2 = c - a;
+        let p = self.direction.cross(e2);
+        let det = e1.dot(p);
+        if ulps_eq!(det, &S::zero()) {
+            // parallel to the triangle's plane
+            return None;
+        }
+
+        let inv_det = S::one() / det;
+        let tv = self.origin - a;
+        let u = tv.dot(p) * inv_det;
+        if u < S::zero() || u > S::one() {
+            return None;
+        }
+
+        let q = tv.cross(e1);
+        let v = self.direction.dot(q) * inv_det;
+        if v < S::zero() || u + v > S::one() {
+            return None;
+        }
+
+        let t = e2.dot(q) * inv_det;
+        if t < S::zero() { None } else { Some(self.point_at(t)) }
+    }
+}
+
 pub type Ray2<S> = Ray<S, Point2<S>, Vector2<S>>;
 pub type Ray3<S> = Ray<S, Point3<S>, Vector3<S>>;
@@ -0,0 +1,164 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Spans of rays inside of solids
+//!
+//! A span is the closed range of ray parameters for which a ray is inside of
+//! a solid. Sets of spans can be combined like the solids themselves, which
+//! is the basis of ray tracing constructive solid geometry.
+
+use std::cmp::Ordering;
+
+use cgmath::BaseFloat;
+
+/// The range of ray parameters from where a ray enters a solid to where it
+/// leaves it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Span<S> {
+    pub enter: S,
+    pub exit: S,
+}
+
+impl<S: BaseFloat> Span<S> {
+    /// Construct a span.
+    #[inline]
+    pub fn new(enter: S, exit: S) -> Span<S> {
+        Span { enter: enter, exit: exit }
+    }
+
+    /// The length of the span, in units of the ray direction. This is the
+    /// thickness of the solid along the ray if the direction is normalized.
+    #[inline]
+    pub fn length(&self) -> S { self.exit - self.enter }
+
+    /// Whether the span includes the ray parameter `t`.
+    #[inline]
+    pub fn contains(&self, t: S) -> bool { self.enter <= t && t <= self.exit }
+
+    /// The part of the span shared with another one.
+    pub fn intersection(&self, other: &Span<S>) -> Option<Span<S>> {
+        let span = Span::new(self.enter.max(other.enter), self.exit.min(other.exit));
+        if span.enter <= span.exit { Some(span) } else { None }
+    }
+}
+
+/// The span of a ray inside of a solid.
+pub trait RaySpan<S, R> {
+    /// Find the span of `ray` inside of `self`. Only the part of the ray from
+    /// its origin on is considered, so the span is entered at `0` if the ray
+    /// starts inside.
+    fn ray_span(&self, ray: &R) -> Option<Span<S>>;
+}
+
+/// A set of disjoint spans, in increasing order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpanSet<S> {
+    spans: Vec<Span<S>>,
+}
+
+impl<S: BaseFloat> SpanSet<S> {
+    /// Construct an empty set.
+    pub fn new() -> SpanSet<S> {
+        SpanSet { spans: Vec::new() }
+    }
+
+    /// Construct a set from spans in any order. Overlapping or touching
+    /// spans are merged.
+    pub fn from_spans(mut spans: Vec<Span<S>>) -> SpanSet<S> {
+        spans.retain(|s| s.enter <= s.exit);
+        spans.sort_by(|a, b| a.enter.partial_cmp(&b.enter).unwrap_or(Ordering::Equal));
+        let mut merged: Vec<Span<S>> = Vec::with_capacity(spans.len());
+        for s in spans {
+            match merged.last_mut() {
+                Some(last) if s.enter <= last.exit => last.exit = last.exit.max(s.exit),
+                _ => merged.push(s),
+            }
+        }
+        SpanSet { spans: merged }
+    }
+
+    /// The spans of the set, in increasing order.
+    #[inline]
+    pub fn spans(&self) -> &[Span<S>] { &self.spans }
+
+    /// Whether the set contains no spans.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.spans.is_empty() }
+
+    /// The first ray parameter in the set, where the ray first enters one
+    /// of the solids.
+    #[inline]
+    pub fn first(&self) -> Option<S> { self.spans.first().map(|s| s.enter) }
+
+    /// The summed length of all spans of the set.
+    pub fn length(&self) -> S {
+        self.spans.iter().fold(S::zero(), |sum, s| sum + s.length())
+    }
+
+    /// The ray parameters inside of either set.
+    pub fn union(&self, other: &SpanSet<S>) -> SpanSet<S> {
+        SpanSet::from_spans(self.spans.iter().chain(other.spans.iter()).cloned().collect())
+    }
+
+    /// The ray parameters inside of both sets.
+    pub fn intersection(&self, other: &SpanSet<S>) -> SpanSet<S> {
+        let mut spans = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.spans.len() && j < other.spans.len() {
+            let (a, b) = (self.spans[i], other.spans[j]);
+            if let Some(s) = a.intersection(&b) {
+                spans.push(s);
+            }
+            if a.exit < b.exit { i += 1 } else { j += 1 }
+        }
+        SpanSet { spans: spans }
+    }
+
+    /// The ray parameters inside of this set but not the other one. Spans
+    /// left with no length are dropped.
+    pub fn difference(&self, other: &SpanSet<S>) -> SpanSet<S> {
+        let mut spans = Vec::new();
+        let mut j = 0;
+        for a in self.spans.iter() {
+            let mut enter = a.enter;
+            while j < other.spans.len() && other.spans[j].exit < enter {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.spans.len() && other.spans[k].enter < a.exit {
+                let b = other.spans[k];
+                if enter < b.enter {
+                    spans.push(Span::new(enter, b.enter));
+                }
+                enter = enter.max(b.exit);
+                k += 1;
+            }
+            if enter < a.exit {
+                spans.push(Span::new(enter, a.exit));
+            }
+        }
+        SpanSet { spans: spans }
+    }
+}
+
+impl<S: BaseFloat> Default for SpanSet<S> {
+    fn default() -> SpanSet<S> { SpanSet::new() }
+}
+
+impl<S: BaseFloat> From<Span<S>> for SpanSet<S> {
+    fn from(span: Span<S>) -> SpanSet<S> {
+        SpanSet::from_spans(vec![span])
+    }
+}
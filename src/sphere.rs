@@ -16,11 +16,12 @@
 //! Bounding sphere
 
 use bound::*;
-use intersect::Intersect;
-use Plane;
-use Ray3;
+use span::{RaySpan, Span};
+use intersect::{Intersect, is_nan3};
+use {Aabb3, Overlap, Plane};
+use {Ray3, Line3};
 use cgmath::{BaseFloat, EuclideanSpace};
-use cgmath::{InnerSpace, Point3};
+use cgmath::{InnerSpace, MetricSpace, Point3};
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 #[cfg_attr(feature = "rustc-serialize", derive(RustcEncodable, RustcDecodable))]
@@ -29,23 +30,82 @@ pub struct Sphere<S: BaseFloat> {
     pub radius: S,
 }
 
+/// Finds the first point where the ray hits the surface of the sphere. If
+/// the ray starts inside the sphere, this is where it leaves the sphere.
 impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Sphere<S>, Ray3<S>) {
     fn intersection(&self) -> Option<Point3<S>> {
         let (ref s, ref r) = *self;
 
+        if is_nan3(s.center.to_vec()) || s.radius.is_nan() || s.radius < S::zero() ||
+           is_nan3(r.origin.to_vec()) || is_nan3(r.direction) {
+            return None;
+        }
         let l = s.center - r.origin;
+        let c = l.dot(l) - s.radius * s.radius;
+        let a = r.direction.dot(r.direction);
+        if a == S::zero() {
+            // a single point
+            return if c <= S::zero() { Some(r.origin) } else { None };
+        }
         let tca = l.dot(r.direction);
-        if tca < S::zero() { return None; }
-        let d2 = l.dot(l) - tca*tca;
-        if d2 > s.radius*s.radius { return None; }
-        let thc = (s.radius*s.radius - d2).sqrt();
-        Some(r.origin + r.direction * (tca - thc))
+        if c > S::zero() && tca < S::zero() { return None; }
+        let discr = tca * tca - a * c;
+        if discr < S::zero() { return None; }
+        let t = if c > S::zero() { tca - discr.sqrt() } else { tca + discr.sqrt() };
+        Some(r.origin + r.direction * (t / a))
+    }
+}
+
+impl<S: BaseFloat> RaySpan<S, Ray3<S>> for Sphere<S> {
+    fn ray_span(&self, ray: &Ray3<S>) -> Option<Span<S>> {
+        let m = ray.origin - self.center;
+        let a = ray.direction.dot(ray.direction);
+        let b = m.dot(ray.direction);
+        let c = m.dot(m) - self.radius * self.radius;
+        if a == S::zero() {
+            // a single point
+            return if c <= S::zero() { Some(Span::new(S::zero(), S::infinity())) } else { None };
+        }
+        let discr = b * b - a * c;
+        if discr < S::zero() {
+            return None;
+        }
+        let root = discr.sqrt();
+        let span = Span::new((-b - root) / a, (-b + root) / a);
+        span.intersection(&Span::new(S::zero(), S::infinity()))
+    }
+}
+
+/// Finds the first point of the segment that lies within the sphere. If the
+/// segment starts inside the sphere, this is the segment's origin.
+///
+/// See _Real-Time Collision Detection_, p. 177 - 179
+impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Sphere<S>, Line3<S>) {
+    fn intersection(&self) -> Option<Point3<S>> {
+        let (ref s, ref l) = *self;
+
+        if is_nan3(s.center.to_vec()) || s.radius.is_nan() || s.radius < S::zero() ||
+           is_nan3(l.origin.to_vec()) || is_nan3(l.dest.to_vec()) {
+            return None;
+        }
+        let m = l.origin - s.center;
+        let d = l.dest - l.origin;
+        let c = m.dot(m) - s.radius * s.radius;
+        if c <= S::zero() { return Some(l.origin); }
+        let a = d.dot(d);
+        if a == S::zero() { return None; }
+        let b = m.dot(d);
+        let discr = b * b - a * c;
+        if discr < S::zero() { return None; }
+        let t = (-b - discr.sqrt()) / a;
+        if t >= S::zero() && t <= S::one() { Some(l.origin + d * t) }
+        else { None }
     }
 }
 
 impl<S: BaseFloat + 'static> Bound<S> for Sphere<S> {
     fn relate_plane(self, plane: Plane<S>) -> Relation {
-        let dist = self.center.dot(plane.n) - plane.d;
+        let dist = plane.distance(self.center);
         if dist > self.radius {
             Relation::In
         } else if dist < - self.radius {
@@ -55,3 +115,45 @@ impl<S: BaseFloat + 'static> Bound<S> for Sphere<S> {
         }
     }
 }
+
+impl<S: BaseFloat + 'static> Relate<Sphere<S>> for Sphere<S> {
+    fn relate(&self, other: &Sphere<S>) -> Relation {
+        let dist = self.center.distance(other.center);
+        if dist > self.radius + other.radius {
+            Relation::Out
+        } else if dist + self.radius < other.radius {
+            Relation::In
+        } else {
+            Relation::Cross
+        }
+    }
+}
+
+impl<S: BaseFloat + 'static> Relate<Aabb3<S>> for Sphere<S> {
+    fn relate(&self, other: &Aabb3<S>) -> Relation {
+        let (c, r) = (self.center, self.radius);
+        if !self.overlaps(other) {
+            Relation::Out
+        } else if other.min.x < c.x - r && c.x + r < other.max.x &&
+                  other.min.y < c.y - r && c.y + r < other.max.y &&
+                  other.min.z < c.z - r && c.z + r < other.max.z {
+            Relation::In
+        } else {
+            Relation::Cross
+        }
+    }
+}
+
+impl<S: BaseFloat + 'static> Relate<Sphere<S>> for Point3<S> {
+    fn relate(&self, other: &Sphere<S>) -> Relation {
+        let dist2 = self.distance2(other.center);
+        let r2 = other.radius * other.radius;
+        if dist2 < r2 {
+            Relation::In
+        } else if dist2 > r2 {
+            Relation::Out
+        } else {
+            Relation::Cross
+        }
+    }
+}
@@ -16,8 +16,8 @@
 extern crate cgmath;
 extern crate collision;
 
-use collision::{Aabb, Aabb2, Aabb3};
-use collision::{Bound, Relation, Plane, Ray};
+use collision::{Aabb, Aabb2, Aabb3, Boundary};
+use collision::{Bound, Relate, Relation, Plane, Ray, Line, Span, Sphere};
 use collision::Intersect;
 use cgmath::InnerSpace;
 use cgmath::{Point2, Point3};
@@ -182,3 +182,303 @@ fn test_bound() {
     assert_eq!(aabb.relate_plane(plane2), Relation::In);
     assert_eq!(aabb.relate_plane(plane3), Relation::Out);
 }
+
+#[test]
+fn test_bound_matches_corners() {
+    let boxes = [
+        Aabb3::new(Point3::new(-5.0f32, 5.0, 0.0), Point3::new(5.0, 10.0, 1.0)),
+        Aabb3::new(Point3::new(1.0f32, 1.0, 1.0), Point3::new(2.0, 2.0, 2.0)),
+        Aabb3::new(Point3::new(-3.0f32, -1.0, -4.0), Point3::new(-1.0, 3.0, 0.5)),
+    ];
+    let planes = [
+        Plane::from_point_normal(Point3::new(0f32, 0.0, 0.0), Vector3::new(1f32, 1.0, 1.0).normalize()),
+        Plane::from_point_normal(Point3::new(1.5f32, 0.0, 0.0), Vector3::new(-1f32, 0.0, 0.0)),
+        Plane::from_point_normal(Point3::new(0f32, 4.0, 0.0), Vector3::new(0f32, -0.6, 0.8)),
+        Plane::from_point_normal(Point3::new(0f32, -2.0, 0.0), Vector3::new(0f32, 1.0, 0.0)),
+    ];
+
+    let mut relations = Vec::new();
+    for plane in planes.iter() {
+        Aabb3::relate_plane_batch(&boxes, *plane, &mut relations);
+        assert_eq!(relations.len(), boxes.len());
+        for (aabb, relation) in boxes.iter().zip(relations.iter()) {
+            let corners: Vec<Relation> = aabb.to_corners().iter().map(|p| p.relate_plane(*plane)).collect();
+            let expected = if corners.iter().all(|r| *r == Relation::In) {
+                Relation::In
+            } else if corners.iter().all(|r| *r == Relation::Out) {
+                Relation::Out
+            } else {
+                Relation::Cross
+            };
+            assert_eq!(aabb.relate_plane(*plane), expected);
+            assert_eq!(*relation, expected);
+        }
+    }
+}
+
+#[test]
+fn test_relate_line() {
+    let aabb = Aabb2::new(Point2::new(-5.0f32, 5.0), Point2::new(5.0, 10.0));
+    assert_eq!(aabb.relate_line(Vector2::new(0.0, 1.0), 0.0), Relation::In);
+    assert_eq!(aabb.relate_line(Vector2::new(0.0, 1.0), 7.0), Relation::Cross);
+    assert_eq!(aabb.relate_line(Vector2::new(0.0, -1.0), 0.0), Relation::Out);
+    assert_eq!(aabb.relate_line(Vector2::new(1.0, 0.0), 5.0), Relation::Cross);
+}
+
+#[test]
+fn test_ray_degenerate() {
+    let nan = std::f32::NAN;
+    let aabb2 = Aabb2::new(Point2::new(1.0f32, 1.0), Point2::new(5.0, 5.0));
+    let aabb3 = Aabb3::new(Point3::new(1.0f32, 1.0, 1.0), Point3::new(5.0, 5.0, 5.0));
+
+    // starting on a slab boundary, moving along it
+    let ray = Ray::new(Point2::new(1.0f32, 0.0), Vector2::new(0.0, 1.0));
+    assert_eq!((ray, aabb2).intersection(), Some(Point2::new(1.0, 1.0)));
+    let ray = Ray::new(Point3::new(1.0f32, 0.0, 5.0), Vector3::new(0.0, 1.0, 0.0));
+    assert_eq!((ray, aabb3).intersection(), Some(Point3::new(1.0, 1.0, 5.0)));
+
+    // parallel to a slab, outside of it
+    let ray = Ray::new(Point2::new(0.0f32, 0.0), Vector2::new(0.0, 1.0));
+    assert_eq!((ray, aabb2).intersection(), None);
+    let ray = Ray::new(Point3::new(2.0f32, 0.0, 6.0), Vector3::new(0.0, 1.0, 0.0));
+    assert_eq!((ray, aabb3).intersection(), None);
+
+    // starting inside, leaving through the boundary
+    let ray = Ray::new(Point3::new(2.0f32, 2.0, 2.0), Vector3::new(1.0, 0.0, 0.0));
+    assert_eq!((ray, aabb3).intersection(), Some(Point3::new(5.0, 2.0, 2.0)));
+
+    // zero direction
+    let ray = Ray::new(Point2::new(2.0f32, 2.0), Vector2::new(0.0, 0.0));
+    assert_eq!((ray, aabb2).intersection(), Some(Point2::new(2.0, 2.0)));
+    let ray = Ray::new(Point2::new(0.0f32, 2.0), Vector2::new(0.0, 0.0));
+    assert_eq!((ray, aabb2).intersection(), None);
+    let ray = Ray::new(Point3::new(2.0f32, 2.0, 2.0), Vector3::new(0.0, 0.0, 0.0));
+    assert_eq!((ray, aabb3).intersection(), Some(Point3::new(2.0, 2.0, 2.0)));
+    let ray = Ray::new(Point3::new(0.0f32, 2.0, 2.0), Vector3::new(0.0, 0.0, 0.0));
+    assert_eq!((ray, aabb3).intersection(), None);
+
+    // touching a corner
+    let ray = Ray::new(Point2::new(0.0f32, 0.0), Vector2::new(1.0, 1.0));
+    assert_eq!((ray, aabb2).intersection(), Some(Point2::new(1.0, 1.0)));
+    let ray = Ray::new(Point2::new(0.0f32, 2.0), Vector2::new(1.0, -1.0));
+    assert_eq!((ray, aabb2).intersection(), Some(Point2::new(1.0, 1.0)));
+
+    // NaN anywhere
+    assert_eq!((Ray::new(Point2::new(nan, 2.0), Vector2::new(1.0, 0.0)), aabb2).intersection(), None);
+    assert_eq!((Ray::new(Point2::new(0.0, 2.0), Vector2::new(nan, 0.0)), aabb2).intersection(), None);
+    assert_eq!((Ray::new(Point3::new(0.0, 2.0, 2.0), Vector3::new(1.0, nan, 0.0)), aabb3).intersection(), None);
+    let bad = Aabb3::new(Point3::new(1.0f32, 1.0, 1.0), Point3::new(5.0, nan, 5.0));
+    assert_eq!((Ray::new(Point3::new(0.0, 2.0, 2.0), Vector3::new(1.0, 0.0, 0.0)), bad).intersection(), None);
+}
+
+#[test]
+fn test_line_degenerate() {
+    let nan = std::f32::NAN;
+    let aabb2 = Aabb2::new(Point2::new(1.0f32, 1.0), Point2::new(5.0, 5.0));
+    let aabb3 = Aabb3::new(Point3::new(1.0f32, 1.0, 1.0), Point3::new(5.0, 5.0, 5.0));
+
+    // zero length
+    let p = Point2::new(2.0f32, 2.0);
+    assert_eq!((Line::new(p, p), aabb2).intersection(), Some(p));
+    let p = Point2::new(0.0f32, 2.0);
+    assert_eq!((Line::new(p, p), aabb2).intersection(), None);
+    let p = Point3::new(2.0f32, 2.0, 5.0);
+    assert_eq!((Line::new(p, p), aabb3).intersection(), Some(p));
+    let p = Point3::new(2.0f32, 2.0, 6.0);
+    assert_eq!((Line::new(p, p), aabb3).intersection(), None);
+
+    // running along a face
+    let line = Line::new(Point3::new(0.0f32, 2.0, 5.0), Point3::new(6.0, 2.0, 5.0));
+    assert_eq!((line, aabb3).intersection(), Some(Point3::new(1.0, 2.0, 5.0)));
+
+    // ending on the boundary
+    let line = Line::new(Point2::new(0.0f32, 2.0), Point2::new(1.0, 2.0));
+    assert_eq!((line, aabb2).intersection(), Some(Point2::new(1.0, 2.0)));
+
+    // starting inside
+    let line = Line::new(Point3::new(2.0f32, 2.0, 2.0), Point3::new(9.0, 2.0, 2.0));
+    assert_eq!((line, aabb3).intersection(), Some(Point3::new(2.0, 2.0, 2.0)));
+
+    // NaN anywhere
+    assert_eq!((Line::new(Point2::new(nan, 2.0), Point2::new(3.0, 2.0)), aabb2).intersection(), None);
+    assert_eq!((Line::new(Point3::new(0.0, 2.0, 2.0), Point3::new(3.0, nan, 2.0)), aabb3).intersection(), None);
+}
+
+#[test]
+fn test_relate() {
+    let outer = Aabb3::new(Point3::new(0, 0, 0), Point3::new(10, 10, 10));
+    assert_eq!(Aabb3::new(Point3::new(1, 1, 1), Point3::new(9, 9, 9)).relate(&outer), Relation::In);
+    assert_eq!(Aabb3::new(Point3::new(0, 1, 1), Point3::new(9, 9, 9)).relate(&outer), Relation::Cross);
+    assert_eq!(Aabb3::new(Point3::new(5, 5, 5), Point3::new(15, 9, 9)).relate(&outer), Relation::Cross);
+    assert_eq!(Aabb3::new(Point3::new(11, 5, 5), Point3::new(15, 9, 9)).relate(&outer), Relation::Out);
+    assert_eq!(outer.relate(&Aabb3::new(Point3::new(1, 1, 1), Point3::new(9, 9, 9))), Relation::Cross);
+
+    let outer = Aabb2::new(Point2::new(0, 0), Point2::new(10, 10));
+    assert_eq!(Aabb2::new(Point2::new(1, 1), Point2::new(9, 9)).relate(&outer), Relation::In);
+    assert_eq!(Aabb2::new(Point2::new(10, 1), Point2::new(12, 9)).relate(&outer), Relation::Cross);
+    assert_eq!(Aabb2::new(Point2::new(11, 1), Point2::new(12, 9)).relate(&outer), Relation::Out);
+    assert_eq!(Point2::new(5, 5).relate(&outer), Relation::In);
+    assert_eq!(Point2::new(0, 5).relate(&outer), Relation::Cross);
+    assert_eq!(Point2::new(-1, 5).relate(&outer), Relation::Out);
+
+    let aabb = Aabb3::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+    let sphere = |x: f32, r: f32| Sphere { center: Point3::new(x, 0.0, 0.0), radius: r };
+    assert_eq!(aabb.relate(&sphere(0.0, 2.0)), Relation::In);
+    assert_eq!(aabb.relate(&sphere(0.0, 1.5)), Relation::Cross);
+    assert_eq!(aabb.relate(&sphere(3.0, 1.5)), Relation::Out);
+    assert_eq!(aabb.relate(&Plane::from_abcd(1.0, 0.0, 0.0, 2.0)), Relation::Out);
+    assert_eq!(Point3::new(0.0, 0.0, 0.0).relate(&aabb), Relation::In);
+    assert_eq!(Point3::new(1.0, 0.0, 0.0).relate(&aabb), Relation::Cross);
+}
+
+#[test]
+fn test_generic() {
+    let a2 = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(2.0, 2.0));
+    let b2 = Aabb2::new(Point2::new(1.0, 1.0), Point2::new(3.0, 4.0));
+    let a3 = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 2.0, 2.0));
+    let b3 = Aabb3::new(Point3::new(1.0, 1.0, 1.0), Point3::new(3.0, 4.0, 5.0));
+
+    assert!(a2.contains(Point2::new(0.0, 1.0)) && !a2.contains(Point2::new(2.0, 1.0)));
+    assert!(a3.contains(Point3::new(0.0, 1.0, 1.0)) && !a3.contains(Point3::new(2.0, 1.0, 1.0)));
+
+    assert_eq!(a2.closest_point(Point2::new(-1.0, 1.0)), Point2::new(0.0, 1.0));
+    assert_eq!(a3.closest_point(Point3::new(-1.0, 1.0, 3.0)), Point3::new(0.0, 1.0, 2.0));
+    assert_eq!(a3.closest_point(Point3::new(0.5, 1.0, 1.5)), Point3::new(0.5, 1.0, 1.5));
+
+    assert_eq!(a2.union(&b2), Aabb2::new(Point2::new(0.0, 0.0), Point2::new(3.0, 4.0)));
+    assert_eq!(a3.union(&b3), Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(3.0, 4.0, 5.0)));
+
+    assert!(a2.intersects(&b2) && a3.intersects(&b3));
+    assert_eq!(a2.intersection(&b2), Some(Aabb2::new(Point2::new(1.0, 1.0), Point2::new(2.0, 2.0))));
+    assert_eq!(a3.intersection(&b3), Some(Aabb3::new(Point3::new(1.0, 1.0, 1.0), Point3::new(2.0, 2.0, 2.0))));
+    let c3 = Aabb3::new(Point3::new(0.0, 0.0, 3.0), Point3::new(1.0, 1.0, 4.0));
+    assert!(!a3.intersects(&c3));
+    assert_eq!(a3.intersection(&c3), None);
+
+    // the same ray in the plane and in space behaves the same way
+    let r2 = Ray::new(Point2::new(-1.0, 1.0), Vector2::new(1.0, 0.0));
+    let r3 = Ray::new(Point3::new(-1.0, 1.0, 1.0), Vector3::new(1.0, 0.0, 0.0));
+    assert_eq!(a2.intersect_ray(&r2), Some(Point2::new(0.0, 1.0)));
+    assert_eq!(a3.intersect_ray(&r3), Some(Point3::new(0.0, 1.0, 1.0)));
+    let r2 = Ray::new(Point2::new(1.0, 1.0), Vector2::new(0.0, 0.0));
+    let r3 = Ray::new(Point3::new(1.0, 1.0, 1.0), Vector3::new(0.0, 0.0, 0.0));
+    assert_eq!(a2.intersect_ray(&r2), Some(Point2::new(1.0, 1.0)));
+    assert_eq!(a3.intersect_ray(&r3), Some(Point3::new(1.0, 1.0, 1.0)));
+    let l2 = Line::new(Point2::new(1.0, -1.0), Point2::new(1.0, 1.0));
+    let l3 = Line::new(Point3::new(1.0, -1.0, 1.0), Point3::new(1.0, 1.0, 1.0));
+    assert_eq!(a2.intersect_line(&l2), Some(Point2::new(1.0, 0.0)));
+    assert_eq!(a3.intersect_line(&l3), Some(Point3::new(1.0, 0.0, 1.0)));
+}
+
+#[test]
+fn test_set_operations() {
+    let a = Aabb3::new(Point3::new(0, 0, 0), Point3::new(4, 2, 6));
+    assert_eq!(a.expand(1), Aabb3::new(Point3::new(-1, -1, -1), Point3::new(5, 3, 7)));
+    assert_eq!(a.expand(-1), Aabb3::new(Point3::new(1, 1, 1), Point3::new(3, 1, 5)));
+    assert_eq!(a.distance2(Point3::new(1, 1, 1)), 0);
+    assert_eq!(a.distance2(Point3::new(-1, 4, 1)), 5);
+    assert_eq!(a.longest_axis(), 2);
+    assert_eq!(Aabb2::new(Point2::new(0, 0), Point2::new(3, 3)).longest_axis(), 0);
+    assert_eq!(a.surface_area(), 2 * (8 + 12 + 24));
+    assert_eq!(Aabb2::new(Point2::new(0, 0), Point2::new(4, 2)).surface_area(), 12);
+
+    let (lo, hi) = a.split(0, 1);
+    assert_eq!(lo, Aabb3::new(Point3::new(0, 0, 0), Point3::new(1, 2, 6)));
+    assert_eq!(hi, Aabb3::new(Point3::new(1, 0, 0), Point3::new(4, 2, 6)));
+    let (lo, hi) = a.split(1, 5);
+    assert_eq!(lo, a);
+    assert_eq!(hi, Aabb3::new(Point3::new(0, 2, 0), Point3::new(4, 2, 6)));
+}
+
+#[test]
+fn test_subtract() {
+    let a = Aabb2::new(Point2::new(0, 0), Point2::new(4, 4));
+    // a hole in the middle leaves a box on every side
+    let parts = a.subtract(&Aabb2::new(Point2::new(1, 1), Point2::new(2, 3)));
+    assert_eq!(parts, vec![Aabb2::new(Point2::new(0, 0), Point2::new(1, 4)),
+                           Aabb2::new(Point2::new(2, 0), Point2::new(4, 4)),
+                           Aabb2::new(Point2::new(1, 0), Point2::new(2, 1)),
+                           Aabb2::new(Point2::new(1, 3), Point2::new(2, 4))]);
+    let area: i32 = parts.iter().map(|p| p.volume()).sum();
+    assert_eq!(area, 16 - 2);
+
+    assert_eq!(a.subtract(&Aabb2::new(Point2::new(2, -1), Point2::new(5, 5))),
+               vec![Aabb2::new(Point2::new(0, 0), Point2::new(2, 4))]);
+    assert_eq!(a.subtract(&Aabb2::new(Point2::new(-1, -1), Point2::new(5, 5))), vec![]);
+    assert_eq!(a.subtract(&Aabb2::new(Point2::new(5, 5), Point2::new(6, 6))), vec![a]);
+    // boxes touching along an edge or at a corner share no area
+    assert_eq!(a.subtract(&Aabb2::new(Point2::new(4, 1), Point2::new(6, 3))), vec![a]);
+    assert_eq!(a.subtract(&Aabb2::new(Point2::new(4, 4), Point2::new(6, 6))), vec![a]);
+
+    let b = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(3.0, 3.0, 3.0));
+    let parts = b.subtract(&Aabb3::new(Point3::new(1.0, 1.0, 1.0), Point3::new(2.0, 2.0, 2.0)));
+    assert_eq!(parts.len(), 6);
+    assert_eq!(parts.iter().map(|p| p.volume()).sum::<f64>(), 26.0);
+}
+
+#[test]
+fn test_boundary() {
+    let a = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(1.0, 1.0));
+    let min = Point2::new(0.0, 0.5);
+    let max = Point2::new(1.0, 0.5);
+    let near = Point2::new(1.0 + 1e-9, 0.5);
+
+    assert!(a.contains_with(min, Boundary::Closed) && a.contains_with(max, Boundary::Closed));
+    assert!(a.contains_with(min, Boundary::HalfOpen) && !a.contains_with(max, Boundary::HalfOpen));
+    assert!(!a.contains_with(min, Boundary::Open) && !a.contains_with(max, Boundary::Open));
+    assert_eq!(a.contains(max), a.contains_with(max, Boundary::HalfOpen));
+    assert!(!a.contains_with(near, Boundary::Closed));
+    assert!(a.contains_with(near, Boundary::Tolerance(1e-6)));
+    assert!(!a.contains_with(Point2::new(0.5, 1e-9), Boundary::Tolerance(-1e-6)));
+
+    let b = Aabb2::new(Point2::new(1.0, 0.0), Point2::new(2.0, 1.0));
+    let c = Aabb2::new(Point2::new(1.0 + 1e-9, 0.0), Point2::new(2.0, 1.0));
+    assert!(a.intersects_with(&b, Boundary::Closed));
+    assert_eq!(a.intersects(&b), a.intersects_with(&b, Boundary::Closed));
+    assert!(!a.intersects_with(&b, Boundary::HalfOpen));
+    assert!(!a.intersects_with(&b, Boundary::Open));
+    assert!(!a.intersects_with(&c, Boundary::Closed));
+    assert!(a.intersects_with(&c, Boundary::Tolerance(1e-6)));
+    let empty = Aabb2::new(Point2::new(0.5, 0.5), Point2::new(0.5, 0.5));
+    assert!(a.intersects_with(&empty, Boundary::Closed));
+    assert!(!a.intersects_with(&empty, Boundary::HalfOpen));
+
+    // grazing rays hit, and touching boxes cross
+    let ray = Ray::new(Point2::new(-1.0, 1.0), Vector2::new(1.0, 0.0));
+    assert_eq!((ray, a).intersection(), Some(Point2::new(0.0, 1.0)));
+    assert_eq!(b.relate(&a), Relation::Cross);
+
+    let ray = Ray::new(Point2::new(-1.0, 1.0), Vector2::new(1.0, 0.0));
+    assert_eq!(a.intersect_ray_with(&ray, Boundary::Closed), Some(Point2::new(0.0, 1.0)));
+    assert_eq!(a.intersect_ray_with(&ray, Boundary::HalfOpen), None);
+    let ray = Ray::new(Point2::new(-1.0, 0.0), Vector2::new(1.0, 0.0));
+    assert_eq!(a.intersect_ray_with(&ray, Boundary::HalfOpen), Some(Point2::new(0.0, 0.0)));
+    assert_eq!(a.intersect_ray_with(&ray, Boundary::Open), None);
+    let ray = Ray::new(Point2::new(-1.0, 0.5), Vector2::new(1.0, 0.0));
+    assert_eq!(a.intersect_ray_with(&ray, Boundary::Open), Some(Point2::new(0.0, 0.5)));
+    assert_eq!(a.ray_span_with(&ray, Boundary::Open), Some(Span::new(1.0, 2.0)));
+    assert_eq!(a.ray_span_with(&ray, Boundary::Tolerance(0.5)), Some(Span::new(0.5, 2.5)));
+
+    // rays only touching a corner
+    let max_corner = Ray::new(Point2::new(0.0, 2.0), Vector2::new(1.0, -1.0));
+    assert_eq!(a.ray_span_with(&max_corner, Boundary::Closed), Some(Span::new(1.0, 1.0)));
+    assert_eq!(a.ray_span_with(&max_corner, Boundary::HalfOpen), None);
+    let min_corner = Ray::new(Point2::new(-1.0, 1.0), Vector2::new(1.0, -1.0));
+    assert_eq!(a.ray_span_with(&min_corner, Boundary::HalfOpen), Some(Span::new(1.0, 1.0)));
+    assert_eq!(a.ray_span_with(&min_corner, Boundary::Open), None);
+
+    let line = Line::new(Point2::new(1.0 + 1e-9, 0.5), Point2::new(3.0, 0.5));
+    assert_eq!(a.intersect_line_with(&line, Boundary::Closed), None);
+    assert_eq!(a.intersect_line_with(&line, Boundary::Tolerance(1e-6)), Some(line.origin));
+
+    // a box resting on an inner face
+    let inner = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(0.5, 0.5));
+    assert_eq!(inner.relate(&a), Relation::Cross);
+    assert_eq!(inner.relate_with(&a, Boundary::Closed), Relation::In);
+    assert_eq!(inner.relate_with(&a, Boundary::HalfOpen), Relation::In);
+    assert_eq!(b.relate_with(&a, Boundary::Closed), Relation::Cross);
+    assert_eq!(b.relate_with(&a, Boundary::HalfOpen), Relation::Out);
+    assert_eq!(c.relate_with(&a, Boundary::Tolerance(1e-6)), Relation::Cross);
+    assert_eq!(empty.relate_with(&a, Boundary::Closed), Relation::In);
+    assert_eq!(empty.relate_with(&a, Boundary::Open), Relation::Out);
+}
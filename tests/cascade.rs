@@ -0,0 +1,82 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[macro_use]
+extern crate approx;
+
+extern crate cgmath;
+extern crate collision;
+
+use cgmath::{Point3, Vector3, PerspectiveFov, Rad, Matrix4};
+use collision::{Cascade, SplitScheme, Projection, Relation, Sphere};
+
+fn fov() -> PerspectiveFov<f64> {
+    PerspectiveFov {
+        fovy: Rad(1.0),
+        aspect: 1.0,
+        near: 1.0,
+        far: 100.0,
+    }
+}
+
+#[test]
+fn test_distances() {
+    assert_eq!(SplitScheme::Uniform.distances(1.0f64, 101.0, 4), vec![1.0, 26.0, 51.0, 76.0, 101.0]);
+    let log = SplitScheme::Logarithmic.distances(1.0f64, 100.0, 2);
+    assert_ulps_eq!(log[1], &10.0);
+    let practical = SplitScheme::Practical(0.5).distances(1.0f64, 100.0, 2);
+    assert_ulps_eq!(practical[1], &(0.5 * 10.0 + 0.5 * 50.5));
+    assert_eq!(practical.len(), 3);
+}
+
+#[test]
+fn test_split() {
+    let points = fov().to_points().unwrap();
+    let cascades = Cascade::split(&points, 1.0, 100.0, 3, SplitScheme::Practical(0.75)).unwrap();
+    assert_eq!(cascades.len(), 3);
+    assert_eq!(cascades[0].points.near_top_left, points.near_top_left);
+    assert_ulps_eq!(cascades[2].points.far_bottom_right, &points.far_bottom_right);
+
+    for c in cascades.iter() {
+        assert_relative_eq!(c.points.near_top_left.z, &-c.near, epsilon = 1e-9);
+        assert_relative_eq!(c.points.far_top_left.z, &-c.far, epsilon = 1e-9);
+        let mid = Sphere { center: Point3::new(0.0, 0.0, -(c.near + c.far) / 2.0), radius: 0.01 };
+        assert_eq!(c.frustum.contains(mid), Relation::In);
+        let before = Sphere { center: Point3::new(0.0, 0.0, -c.near + 0.5), radius: 0.01 };
+        assert_eq!(c.frustum.contains(before), Relation::Out);
+    }
+
+    assert!(Cascade::split(&points, 10.0, 10.0, 3, SplitScheme::Uniform).is_none());
+    assert!(Cascade::split(&points, 20.0, 10.0, 3, SplitScheme::Uniform).is_none());
+    assert!(Cascade::split(&points, std::f64::NAN, 10.0, 3, SplitScheme::Uniform).is_none());
+}
+
+#[test]
+fn test_light_projection() {
+    let points = fov().to_points().unwrap();
+    let cascades = Cascade::split(&points, 1.0, 100.0, 2, SplitScheme::Uniform).unwrap();
+    let light = Vector3::new(1.0, -2.0, 0.5);
+    let (view, ortho) = cascades[0].light_projection(light, 1024.0);
+
+    let texel = (ortho.right - ortho.left) / 1024.0;
+    assert_relative_eq!((ortho.left / texel).round() * texel, &ortho.left, epsilon = 1e-9);
+    assert_relative_eq!(ortho.top - ortho.bottom, &(ortho.right - ortho.left), epsilon = 1e-9);
+
+    let proj: Matrix4<f64> = ortho.into();
+    for c in cascades[0].points.to_corners().iter() {
+        let p = proj * view * c.to_homogeneous();
+        assert!(p.x.abs() <= 1.0 && p.y.abs() <= 1.0 && p.z.abs() <= 1.0 + 1e-9, "{:?}", p);
+    }
+}
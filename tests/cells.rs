@@ -0,0 +1,106 @@
+// Copyright 2015 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+extern crate collision;
+
+use std::{f32, f64};
+
+use cgmath::{Point2, Point3, Vector2, Vector3};
+use collision::{Aabb, Aabb2, Aabb3, Boundary, Ray, RayCells};
+
+#[test]
+fn test_iterate() {
+    let a = Aabb2::new(Point2::new(-1, 2), Point2::new(1, 4));
+    let cells: Vec<Point2<i32>> = a.cells().collect();
+    assert_eq!(cells, vec![Point2::new(-1, 2), Point2::new(0, 2),
+                           Point2::new(-1, 3), Point2::new(0, 3)]);
+
+    let b = Aabb3::new(Point3::new(0u8, 0, 0), Point3::new(2, 3, 4));
+    assert_eq!(b.cells().count(), 24);
+    assert!(b.cells().all(|c| b.contains(c)));
+
+    let flat = Aabb2::new(Point2::new(0, 0), Point2::new(0, 5));
+    assert_eq!(flat.cells().count(), 0);
+}
+
+#[test]
+fn test_integer_overlap() {
+    let a = Aabb2::new(Point2::new(0, 0), Point2::new(4, 4));
+    let b = Aabb2::new(Point2::new(2, 3), Point2::new(6, 8));
+    let c = Aabb2::new(Point2::new(4, 0), Point2::new(6, 2));
+
+    assert_eq!(a.intersection(&b), Some(Aabb2::new(Point2::new(2, 3), Point2::new(4, 4))));
+    assert_eq!(a.intersection(&b).unwrap().cells().count(), 2);
+
+    // neighbouring tiles touch but share no cells
+    assert!(a.intersects(&c));
+    assert!(!a.intersects_with(&c, Boundary::HalfOpen));
+    assert_eq!(a.intersection(&c).unwrap().cells().count(), 0);
+}
+
+#[test]
+fn test_convert() {
+    let f = Aabb2::new(Point2::new(-0.5f32, 1.0), Point2::new(2.5, 3.0));
+    assert_eq!(f.round_out(), Some(Aabb2::new(Point2::new(-1, 1), Point2::new(3, 3))));
+    assert_eq!(f.round_in(), Some(Aabb2::new(Point2::new(0, 1), Point2::new(2, 3))));
+    assert_eq!(f.cell_range(), Some(Aabb2::new(Point2::new(-1, 1), Point2::new(3, 4))));
+
+    let thin = Aabb3::new(Point3::new(0.2f64, 0.0, 0.0), Point3::new(0.8, 2.0, 2.0));
+    let inner = thin.round_in::<i32>().unwrap();
+    assert_eq!(inner.cells().count(), 0);
+    assert_eq!(thin.cell_range(), Some(Aabb3::new(Point3::new(0, 0, 0), Point3::new(1, 3, 3))));
+
+    let i = Aabb3::new(Point3::new(-2, 0, 1), Point3::new(3, 4, 5));
+    let back = i.cast::<f32>().unwrap();
+    assert_eq!(back, Aabb3::new(Point3::new(-2.0, 0.0, 1.0), Point3::new(3.0, 4.0, 5.0)));
+    assert_eq!(back.round_out(), Some(i));
+
+    assert_eq!(f.round_out::<u32>(), None);
+    assert_eq!(Aabb2::new(Point2::new(0.0, 0.0), Point2::new(1e20f32, 1.0)).cast::<i32>(), None);
+}
+
+#[test]
+fn test_ray_cells() {
+    let ray = Ray::new(Point2::new(0.5f32, 0.5), Vector2::new(1.0, 0.5));
+    assert_eq!(ray.cells::<i32>(2.8).collect::<Vec<_>>(),
+               vec![Point2::new(0, 0), Point2::new(1, 0), Point2::new(1, 1),
+                    Point2::new(2, 1), Point2::new(3, 1)]);
+
+    let back = Ray::new(Point2::new(0.5f32, 0.5), Vector2::new(-1.0, 0.0));
+    assert_eq!(back.cells::<i32>(2.0).collect::<Vec<_>>(),
+               vec![Point2::new(0, 0), Point2::new(-1, 0), Point2::new(-2, 0)]);
+    // cells below zero cannot be represented
+    assert_eq!(back.cells::<u32>(2.0).collect::<Vec<_>>(), vec![Point2::new(0, 0)]);
+
+    let still = Ray::new(Point2::new(1.5f32, -0.5), Vector2::new(0.0, 0.0));
+    assert_eq!(still.cells::<i32>(10.0).collect::<Vec<_>>(), vec![Point2::new(1, -1)]);
+    assert_eq!(still.cells::<i32>(f32::INFINITY).count(), 1);
+
+    let ray = Ray::new(Point3::new(0.5f64, 0.5, 0.5), Vector3::new(0.0, 0.0, 1.0));
+    assert_eq!(ray.cells::<i32>(2.0).collect::<Vec<_>>(),
+               vec![Point3::new(0, 0, 0), Point3::new(0, 0, 1), Point3::new(0, 0, 2)]);
+    assert_eq!(ray.cells::<i32>(-1.0).count(), 0);
+    assert_eq!(ray.cells::<i64>(f64::INFINITY).count(), 0);
+    assert_eq!(ray.cells::<i16>(f64::NAN).count(), 0);
+}
+
+#[test]
+fn test_ray_cells_sized() {
+    let cells: RayCells<f32, Point2<f32>, Point2<i32>> =
+        RayCells::new(Point2::new(-1.0, 1.0), Vector2::new(1.0, 0.0), 2.0, 0.5, 4.5);
+    assert_eq!(cells.collect::<Vec<_>>(),
+               vec![Point2::new(-1, 0), Point2::new(0, 0), Point2::new(1, 0)]);
+}
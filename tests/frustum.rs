@@ -13,11 +13,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[macro_use]
+extern crate approx;
+
 extern crate collision;
 extern crate cgmath;
 
-use cgmath::{Point3, PerspectiveFov, Rad};
-use collision::{Projection, Relation, Sphere};
+use cgmath::{Point2, Point3, Vector3, PerspectiveFov, Perspective, Ortho, Rad, Matrix4, InnerSpace};
+use cgmath::{Quaternion, Rotation3, SquareMatrix};
+use collision::{Aabb, Aabb3, Projection, Relation, Sphere, Frustum, FrustumPoints, PlaneMask};
+use collision::{DepthRange, Line3, Obb3, Plane, Ray3};
+
+fn assert_points_eq(a: FrustumPoints<f32>, b: FrustumPoints<f32>) {
+    for (p, q) in a.to_corners().iter().zip(b.to_corners().iter()) {
+        assert!(relative_eq!(p, q, epsilon = 1e-4), "{:?} != {:?}", a, b);
+    }
+}
 
 #[test]
 fn test_contains() {
@@ -40,3 +51,322 @@ fn test_contains() {
             radius: 1f32,
         }), Relation::Out);
 }
+
+#[test]
+fn test_points() {
+    let persp = Perspective {
+        left: -1f32, right: 1f32,
+        bottom: -0.5f32, top: 0.5f32,
+        near: 1f32, far: 10f32,
+    };
+    let points = persp.to_points().unwrap();
+    assert_eq!(points.near_top_left, Point3::new(-1.0, 0.5, -1.0));
+    assert_eq!(points.far_bottom_right, Point3::new(10.0, -5.0, -10.0));
+
+    let mat: Matrix4<f32> = persp.into();
+    assert_points_eq(FrustumPoints::from_matrix4(mat).unwrap(), points);
+    assert_points_eq(Frustum::from_matrix4(mat).unwrap().to_points().unwrap(), points);
+
+    let fov = PerspectiveFov {
+        fovy: Rad(1f32),
+        aspect: 1.5f32,
+        near: 0.5f32,
+        far: 20f32,
+    };
+    assert_points_eq(FrustumPoints::from_matrix4(fov.into()).unwrap(), fov.to_points().unwrap());
+    assert_points_eq(fov.to_frustum().to_points().unwrap(), fov.to_points().unwrap());
+
+    let ortho = Ortho {
+        left: -2f32, right: 3f32,
+        bottom: -1f32, top: 1f32,
+        near: 1f32, far: 5f32,
+    };
+    assert_points_eq(FrustumPoints::from_matrix4(ortho.into()).unwrap(), ortho.to_points().unwrap());
+}
+
+/// A projection implementing only the required methods of `Projection`.
+#[derive(Copy, Clone)]
+struct Custom(Matrix4<f32>);
+
+impl From<Custom> for Matrix4<f32> {
+    fn from(c: Custom) -> Matrix4<f32> { c.0 }
+}
+
+impl Projection<f32> for Custom {
+    fn to_frustum(&self) -> Frustum<f32> { Frustum::from_matrix4(self.0).unwrap() }
+}
+
+#[test]
+fn test_points_default() {
+    let fov = PerspectiveFov { fovy: Rad(1f32), aspect: 1.5, near: 0.5, far: 20.0 };
+    assert_points_eq(Custom(fov.into()).to_points().unwrap(), fov.to_points().unwrap());
+
+    let infinite = PerspectiveFov { far: std::f32::INFINITY, ..fov };
+    assert!(Custom(infinite.into()).to_points().is_none());
+    assert!(infinite.to_points().is_none());
+}
+
+#[test]
+fn test_points_bounds() {
+    let ortho = Ortho {
+        left: -2f32, right: 3f32,
+        bottom: -1f32, top: 1f32,
+        near: 1f32, far: 5f32,
+    };
+    let points = ortho.to_points().unwrap();
+    let aabb = points.to_aabb();
+    assert_eq!(aabb.min(), Point3::new(-2.0, -1.0, -5.0));
+    assert_eq!(aabb.max(), Point3::new(3.0, 1.0, -1.0));
+
+    let sphere = points.to_sphere();
+    assert_eq!(sphere.center, Point3::new(0.5, 0.0, -3.0));
+    for p in points.to_corners().iter() {
+        assert!((p - sphere.center).magnitude() <= sphere.radius + 1e-5);
+    }
+}
+
+#[test]
+fn test_contains_masked() {
+    let frustum = PerspectiveFov {
+        fovy: Rad(1f32),
+        aspect: 1f32,
+        near: 1f32,
+        far: 10f32,
+    }.to_frustum();
+
+    let parent = Aabb3::new(Point3::new(-1f32, -1.0, -6.0), Point3::new(1.0, 1.0, -12.0));
+    let mut last_out = 0;
+    let (relation, mask) = frustum.contains_masked(parent, PlaneMask::all(), &mut last_out);
+    assert_eq!(relation, Relation::Cross);
+    assert_eq!(mask, PlaneMask::none().with(5)); // only crosses the far plane
+
+    let inside = Sphere { center: Point3::new(0f32, 0.0, -7.0), radius: 0.5 };
+    let crossing = Sphere { center: Point3::new(0f32, 0.0, -10.0), radius: 0.5 };
+    assert_eq!(frustum.contains_masked(inside, mask, &mut last_out), (Relation::In, PlaneMask::none()));
+    assert_eq!(frustum.contains_masked(crossing, mask, &mut last_out).0, Relation::Cross);
+
+    // a child outside of a plane the parent was inside of is not tested
+    // against that plane
+    let beside = Sphere { center: Point3::new(20f32, 0.0, -7.0), radius: 0.5 };
+    assert_eq!(frustum.contains_masked(beside, mask, &mut last_out).0, Relation::In);
+    assert_eq!(frustum.contains_masked(beside, PlaneMask::all(), &mut last_out).0, Relation::Out);
+    assert_eq!(last_out, 1); // rejected by the right plane
+
+    // all masks agree with the unmasked test
+    for b in [inside, crossing, beside].iter() {
+        let mut last_out = 4;
+        assert_eq!(frustum.contains_masked(*b, PlaneMask::all(), &mut last_out).0, frustum.contains(*b));
+    }
+}
+
+/// A frustum with a diamond shaped cross-section, `|x - offset| + |y| <= 1`,
+/// from `z = -1` to `z = -10`.
+fn diamond(offset: f32) -> Frustum<f32> {
+    let plane = |x: f32, y: f32, z: f32, n: Vector3<f32>| Plane::from_point_normal(Point3::new(x, y, z), n.normalize());
+    Frustum::new(
+        plane(offset - 1.0, 0.0, 0.0, Vector3::new(1.0, 1.0, 0.0)),
+        plane(offset + 1.0, 0.0, 0.0, Vector3::new(-1.0, -1.0, 0.0)),
+        plane(offset + 1.0, 0.0, 0.0, Vector3::new(-1.0, 1.0, 0.0)),
+        plane(offset - 1.0, 0.0, 0.0, Vector3::new(1.0, -1.0, 0.0)),
+        plane(0.0, 0.0, -1.0, Vector3::new(0.0, 0.0, -1.0)),
+        plane(0.0, 0.0, -10.0, Vector3::new(0.0, 0.0, 1.0)),
+    )
+}
+
+#[test]
+fn test_overlaps() {
+    let frustum = diamond(0.0);
+
+    // crosses two of the planes, but is separated by its own face
+    let aabb = Aabb3::new(Point3::new(1.2, -2.0, -6.0), Point3::new(2.0, 2.0, -4.0));
+    assert_eq!(frustum.contains(aabb), Relation::Cross);
+    assert!(!frustum.overlaps_aabb(&aabb));
+    let aabb = Aabb3::new(Point3::new(0.8, -2.0, -6.0), Point3::new(2.0, 2.0, -4.0));
+    assert!(frustum.overlaps_aabb(&aabb));
+
+    let rotation = Vector3::new(0.0, 0.0, std::f32::consts::FRAC_PI_4);
+    let extents = Vector3::new(1.0, 1.0, 1.0);
+    assert!(!frustum.overlaps_obb(&Obb3::new(Point3::new(2.5, 0.0, -5.0), rotation, extents)));
+    assert!(frustum.overlaps_obb(&Obb3::new(Point3::new(1.5, 0.0, -5.0), rotation, extents)));
+
+    let perspective = PerspectiveFov {
+        fovy: Rad(std::f32::consts::FRAC_PI_2),
+        aspect: 1.0,
+        near: 1.0,
+        far: 10.0,
+    }.to_frustum();
+    assert!(frustum.overlaps_frustum(&perspective));
+    assert!(diamond(5.0).overlaps_frustum(&perspective));
+    assert!(!diamond(15.0).overlaps_frustum(&perspective));
+    assert!(!diamond(2.5).overlaps_frustum(&frustum));
+    assert!(diamond(1.5).overlaps_frustum(&frustum));
+}
+
+#[test]
+fn test_clip() {
+    let frustum = diamond(0.0);
+    let ray = Ray3::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0));
+    let (t0, t1) = frustum.clip_ray(&ray).unwrap();
+    assert_ulps_eq!(t0, 1.0);
+    assert_ulps_eq!(t1, 10.0);
+
+    let (t0, t1) = frustum.clip_line(&Line3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, -20.0))).unwrap();
+    assert_ulps_eq!(t0, 0.05);
+    assert_ulps_eq!(t1, 0.5);
+
+    let ray = Ray3::new(Point3::new(5.0, 0.0, -5.0), Vector3::new(-1.0, 0.0, 0.0));
+    let (t0, t1) = frustum.clip_ray(&ray).unwrap();
+    assert_relative_eq!(t0, 4.0, epsilon = 1e-5);
+    assert_relative_eq!(t1, 6.0, epsilon = 1e-5);
+
+    let ray = Ray3::new(Point3::new(0.0, 0.0, -5.0), Vector3::new(1.0, 0.0, 0.0));
+    assert_eq!(frustum.clip_ray(&ray).map(|t| t.0), Some(0.0));
+    assert_eq!(frustum.clip_ray(&Ray3::new(Point3::new(5.0, 0.0, -5.0), Vector3::new(0.0, 1.0, 0.0))), None);
+    assert_eq!(frustum.clip_ray(&Ray3::new(Point3::new(5.0, 0.0, -5.0), Vector3::new(1.0, 0.0, 0.0))), None);
+}
+
+fn assert_frustum_eq(a: Frustum<f32>, b: Frustum<f32>) {
+    for (p, q) in a.planes().iter().zip(b.planes().iter()) {
+        assert!(relative_eq!(p, q, epsilon = 1e-4), "{:?} != {:?}", a, b);
+    }
+}
+
+#[test]
+fn test_camera() {
+    let proj = PerspectiveFov {
+        fovy: Rad(std::f32::consts::FRAC_PI_2),
+        aspect: 1.5,
+        near: 1.0,
+        far: 10.0,
+    };
+    let eye = Point3::new(1.0, 2.0, 5.0);
+    let view = Matrix4::look_at(eye, Point3::new(1.0, 2.0, 0.0), Vector3::unit_y());
+    let expected = Frustum::from_matrix4(Matrix4::from(proj) * view).unwrap();
+
+    assert_frustum_eq(proj.to_frustum().to_world(view).unwrap(), expected);
+    let frustum = Frustum::from_camera(eye, Quaternion::from_angle_y(Rad(0.0)), proj.fovy, 1.5, 1.0, 10.0).unwrap();
+    assert_frustum_eq(frustum, expected);
+
+    // turned left, looking down the negative x axis
+    let frustum = Frustum::from_camera(Point3::new(0.0, 0.0, 0.0),
+                                       Quaternion::from_angle_y(Rad(std::f32::consts::FRAC_PI_2)),
+                                       proj.fovy, 1.5, 1.0, 10.0).unwrap();
+    assert_eq!(frustum.contains(Point3::new(-5.0, 0.0, 0.0)), Relation::In);
+    assert_eq!(frustum.contains(Point3::new(5.0, 0.0, 0.0)), Relation::Out);
+    assert_eq!(frustum.contains(Point3::new(0.0, 0.0, -5.0)), Relation::Out);
+
+    assert_eq!(frustum.to_world(Matrix4::from_value(0.0)), None);
+    assert_frustum_eq(frustum.to_world(Matrix4::identity()).unwrap(), frustum);
+
+    let q = Quaternion::from_angle_y(Rad(0.0));
+    assert_eq!(Frustum::from_camera(eye, q, proj.fovy, 1.5, 0.0, 10.0), None);
+    assert_eq!(Frustum::from_camera(eye, q, proj.fovy, 1.5, 10.0, 1.0), None);
+    assert_eq!(Frustum::from_camera(eye, q, proj.fovy, 0.0, 1.0, 10.0), None);
+    assert_eq!(Frustum::from_camera(eye, q, Rad(4.0), 1.5, 1.0, 10.0), None);
+    assert!(Frustum::from_camera(eye, q, proj.fovy, 1.5, 1.0, std::f32::INFINITY).unwrap().is_infinite());
+}
+
+#[test]
+fn test_rect() {
+    let mat: Matrix4<f32> = PerspectiveFov {
+        fovy: Rad(std::f32::consts::FRAC_PI_2),
+        aspect: 1.0,
+        near: 1.0,
+        far: 10.0,
+    }.into();
+    let full = Frustum::from_matrix4_rect(mat, DepthRange::NegativeOneToOne, Point2::new(-1.0, -1.0), Point2::new(1.0, 1.0)).unwrap();
+    assert_frustum_eq(full, Frustum::from_matrix4(mat).unwrap());
+
+    // the upper right quarter of the screen
+    let rect = Frustum::from_matrix4_rect(mat, DepthRange::NegativeOneToOne, Point2::new(0.0, 0.0), Point2::new(1.0, 1.0)).unwrap();
+    assert_eq!(rect.contains(Point3::new(1.0, 1.0, -5.0)), Relation::In);
+    assert_eq!(rect.contains(Point3::new(4.0, 4.0, -5.0)), Relation::In);
+    assert_eq!(rect.contains(Point3::new(-1.0, 1.0, -5.0)), Relation::Out);
+    assert_eq!(rect.contains(Point3::new(1.0, -1.0, -5.0)), Relation::Out);
+    assert_eq!(rect.contains(Point3::new(6.0, 1.0, -5.0)), Relation::Out);
+
+    // a click rather than a drag
+    let click = Point2::new(0.25, 0.5);
+    assert!(Frustum::from_matrix4_rect(mat, DepthRange::NegativeOneToOne, click, click).is_none());
+    assert!(Frustum::from_matrix4_rect(mat, DepthRange::NegativeOneToOne, click, Point2::new(0.5, 0.5)).is_none());
+    assert!(Frustum::from_matrix4_rect(mat, DepthRange::NegativeOneToOne, click, Point2::new(std::f32::NAN, 1.0)).is_none());
+}
+
+/// A perspective projection with a 90 degree field of view, mapping view
+/// space depth `z` to a clip space depth of `a * z + b`.
+fn depth_projection(a: f32, b: f32) -> Matrix4<f32> {
+    Matrix4::new(1.0, 0.0, 0.0, 0.0,
+                 0.0, 1.0, 0.0, 0.0,
+                 0.0, 0.0, a, -1.0,
+                 0.0, 0.0, b, 0.0)
+}
+
+#[test]
+fn test_depth_range() {
+    let (n, f) = (1.0, 10.0);
+    let gl = depth_projection((f + n) / (n - f), 2.0 * f * n / (n - f));
+    let d3d = depth_projection(f / (n - f), f * n / (n - f));
+    let reversed = depth_projection(n / (f - n), f * n / (f - n));
+
+    let expected = Frustum::from_matrix4(gl).unwrap();
+    assert!(!expected.is_infinite());
+    assert_frustum_eq(Frustum::from_matrix4_depth(gl, DepthRange::NegativeOneToOne).unwrap(), expected);
+    assert_frustum_eq(Frustum::from_matrix4_depth(d3d, DepthRange::ZeroToOne).unwrap(), expected);
+    assert_frustum_eq(Frustum::from_matrix4_depth(reversed, DepthRange::ReversedZeroToOne).unwrap(), expected);
+
+    let points = FrustumPoints::from_matrix4(gl).unwrap();
+    assert_points_eq(FrustumPoints::from_matrix4_depth(d3d, DepthRange::ZeroToOne).unwrap(), points);
+    assert_points_eq(FrustumPoints::from_matrix4_depth(reversed, DepthRange::ReversedZeroToOne).unwrap(), points);
+}
+
+#[test]
+fn test_infinite() {
+    let projections = [
+        (depth_projection(-1.0, -2.0), DepthRange::NegativeOneToOne),
+        (depth_projection(-1.0, -1.0), DepthRange::ZeroToOne),
+        (depth_projection(0.0, 1.0), DepthRange::ReversedZeroToOne),
+    ];
+    for &(mat, depth) in projections.iter() {
+        let frustum = Frustum::from_matrix4_depth(mat, depth).unwrap();
+        assert!(frustum.is_infinite());
+        assert_eq!(frustum.planes().len(), 5);
+        assert_eq!(frustum.to_points(), None);
+        assert_eq!(FrustumPoints::from_matrix4_depth(mat, depth), None);
+
+        assert_eq!(frustum.contains(Point3::new(0.0, 0.0, -0.5)), Relation::Out);
+        assert_eq!(frustum.contains(Point3::new(0.0, 0.0, -5.0)), Relation::In);
+        assert_eq!(frustum.contains(Point3::new(0.0, 0.0, -1e6)), Relation::In);
+        assert_eq!(frustum.contains(Point3::new(2e6, 0.0, -1e6)), Relation::Out);
+
+        let mut last_out = 5;
+        let aabb = Aabb3::new(Point3::new(-1.0, -1.0, -1e6), Point3::new(1.0, 1.0, -1e5));
+        assert_eq!(frustum.contains_masked(aabb, PlaneMask::all(), &mut last_out).0, Relation::In);
+        assert!(frustum.overlaps_aabb(&aabb));
+    }
+
+    let fov = PerspectiveFov { fovy: Rad(std::f32::consts::FRAC_PI_2), aspect: 1.0, near: 1.0, far: std::f32::INFINITY };
+    assert_eq!(Frustum::from_matrix4(fov.into()), None);
+    let persp = Perspective { left: -1.0, right: 1.0, bottom: -1.0, top: 1.0, near: 1.0, far: std::f32::INFINITY };
+    for frustum in [fov.to_frustum(), persp.to_frustum()].iter() {
+        assert!(frustum.is_infinite());
+        assert_eq!(frustum.contains(Point3::new(0.0, 0.0, -0.5)), Relation::Out);
+        assert_eq!(frustum.contains(Point3::new(0.0, 0.0, -1e6)), Relation::In);
+        assert_eq!(frustum.contains(Point3::new(2e6, 0.0, -1e6)), Relation::Out);
+    }
+}
+
+#[test]
+fn test_ortho_frustum() {
+    let ortho = Ortho {
+        left: -2.0f32, right: 3.0,
+        bottom: -1.0, top: 4.0,
+        near: 1.0, far: 10.0,
+    };
+    let frustum = ortho.to_frustum();
+    assert_frustum_eq(frustum, Frustum::from_matrix4(ortho.into()).unwrap());
+    assert_eq!(frustum.contains(Point3::new(2.5, 3.5, -9.5)), Relation::In);
+    assert_eq!(frustum.contains(Point3::new(3.5, 0.0, -5.0)), Relation::Out);
+    assert_eq!(frustum.contains(Point3::new(0.0, 4.5, -5.0)), Relation::Out);
+    assert_eq!(frustum.contains(Point3::new(0.0, 0.0, -10.5)), Relation::Out);
+    assert_points_eq(frustum.to_points().unwrap(), ortho.to_points().unwrap());
+}
@@ -0,0 +1,83 @@
+// Copyright 2015 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+
+extern crate cgmath;
+extern crate collision;
+
+use std::f64::consts::PI;
+
+use cgmath::*;
+use collision::*;
+
+#[test]
+fn test_half_plane() {
+    let h = HalfPlane::from_points(Point2::new(0.0, 1.0), Point2::new(1.0, 1.0)).unwrap();
+    assert_ulps_eq!(h.n, &Vector2::new(0.0, 1.0));
+    assert_ulps_eq!(h.d, &1.0);
+    assert_eq!(h.distance(Point2::new(5.0, 3.0)), 2.0);
+    assert_eq!(h.flip().distance(Point2::new(5.0, 3.0)), -2.0);
+    assert_eq!(HalfPlane::from_points(Point2::new(1.0, 1.0), Point2::new(1.0, 1.0)), None);
+    assert_eq!(HalfPlane::new(Vector2::new(0.0, 2.0), 4.0).normalize(),
+               Some(HalfPlane::new(Vector2::new(0.0, 1.0), 2.0)));
+}
+
+#[test]
+fn test_bound() {
+    let h = HalfPlane::new(Vector2::unit_x(), 1.0);
+    assert_eq!(Point2::new(2.0, 0.0).relate_half_plane(h), Relation::In);
+    assert_eq!(Point2::new(1.0, 0.0).relate_half_plane(h), Relation::Cross);
+    assert_eq!(Point2::new(0.0, 0.0).relate_half_plane(h), Relation::Out);
+
+    let aabb = |x: f64| Aabb2::new(Point2::new(x - 0.5, -0.5), Point2::new(x + 0.5, 0.5));
+    assert_eq!(aabb(2.0).relate_half_plane(h), Relation::In);
+    assert_eq!(aabb(1.0).relate_half_plane(h), Relation::Cross);
+    assert_eq!(aabb(0.0).relate_half_plane(h), Relation::Out);
+
+    let circle = |x: f64| Circle { center: Point2::new(x, 0.0), radius: 0.5 };
+    assert_eq!(circle(2.0).relate_half_plane(h), Relation::In);
+    assert_eq!(circle(1.2).relate(&h), Relation::Cross);
+    assert_eq!(circle(0.0).relate(&h), Relation::Out);
+}
+
+#[test]
+fn test_ortho() {
+    let frustum = Frustum2::from_ortho(&Ortho { left: -4.0, right: 4.0, bottom: -2.0, top: 2.0,
+                                                near: 0.0, far: 1.0 });
+    assert_eq!(frustum.contains(Point2::new(0.0, 0.0)), Relation::In);
+    assert_eq!(frustum.contains(Point2::new(5.0, 0.0)), Relation::Out);
+    assert_eq!(frustum.contains(Aabb2::new(Point2::new(-1.0, -1.0), Point2::new(1.0, 1.0))), Relation::In);
+    assert_eq!(frustum.contains(Aabb2::new(Point2::new(3.0, 1.0), Point2::new(5.0, 3.0))), Relation::Cross);
+    assert_eq!(frustum.contains(Circle { center: Point2::new(0.0, 3.0), radius: 0.5 }), Relation::Out);
+    assert_eq!(Circle { center: Point2::new(0.0, 2.0), radius: 0.5 }.relate(&frustum), Relation::Cross);
+}
+
+#[test]
+fn test_camera() {
+    // a 4 by 2 view rotated by a quarter turn, so it is 2 wide and 4 high
+    let frustum = Frustum2::from_camera(Point2::new(10.0, 0.0), Rad(PI / 2.0), Vector2::new(2.0, 1.0));
+    assert_eq!(frustum.contains(Point2::new(10.0, 1.9)), Relation::In);
+    assert_eq!(frustum.contains(Point2::new(11.5, 0.0)), Relation::Out);
+    assert_eq!(frustum.contains(Aabb2::new(Point2::new(9.5, -1.0), Point2::new(10.5, 1.0))), Relation::In);
+    assert_eq!(frustum.contains(Aabb2::new(Point2::new(9.5, 1.0), Point2::new(10.5, 3.0))), Relation::Cross);
+    assert_eq!(frustum.contains(Aabb2::new(Point2::new(7.0, -1.0), Point2::new(8.5, 1.0))), Relation::Out);
+
+    // a diamond seen by a camera rotated by an eighth of a turn
+    let frustum = Frustum2::from_camera(Point2::new(0.0, 0.0), Rad(PI / 4.0), Vector2::new(1.0, 1.0));
+    assert_eq!(frustum.contains(Point2::new(1.3, 0.0)), Relation::In);
+    assert_eq!(frustum.contains(Point2::new(1.0, 1.0)), Relation::Out);
+    assert_eq!(frustum.contains(Circle { center: Point2::new(0.9, 0.9), radius: 0.2 }), Relation::Out);
+}
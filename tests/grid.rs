@@ -0,0 +1,145 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+extern crate collision;
+
+use std::{f32, i32};
+
+use cgmath::{Point2, Point3, Vector2, Vector3};
+use collision::{Aabb2, Aabb3, Grid2, Grid3, Ray, Sphere};
+
+fn square(x: f32, y: f32) -> Aabb2<f32> {
+    Aabb2::new(Point2::new(x, y), Point2::new(x + 1.0, y + 1.0))
+}
+
+fn cube(x: f32, y: f32, z: f32) -> Aabb3<f32> {
+    Aabb3::new(Point3::new(x, y, z), Point3::new(x + 1.0, y + 1.0, z + 1.0))
+}
+
+fn sorted(mut v: Vec<u32>) -> Vec<u32> {
+    v.sort();
+    v
+}
+
+#[test]
+fn test_insert_remove() {
+    let mut grid: Grid2<f32, u32> = Grid2::hashed(2.0);
+    assert!(grid.is_empty());
+    assert_eq!(grid.insert(1, square(0.5, 0.5)), None);
+    assert_eq!(grid.insert(2, square(10.0, 10.0)), None);
+    assert_eq!(grid.len(), 2);
+
+    assert_eq!(grid.query_bound(&square(0.0, 0.0)), vec![1]);
+    assert_eq!(grid.insert(1, square(20.0, 20.0)), Some(square(0.5, 0.5)));
+    assert_eq!(grid.query_bound(&square(0.0, 0.0)), vec![]);
+    assert!(grid.update(2, square(-5.0, -5.0)));
+    assert!(!grid.update(3, square(-5.0, -5.0)));
+    assert_eq!(grid.query_bound(&square(-4.5, -4.5)), vec![2]);
+
+    assert_eq!(grid.remove(&2), Some(square(-5.0, -5.0)));
+    assert_eq!(grid.remove(&2), None);
+    assert_eq!(grid.query_bound(&square(-4.5, -4.5)), vec![]);
+    assert_eq!(grid.get(&1), Some(&square(20.0, 20.0)));
+}
+
+#[test]
+fn test_queries() {
+    let bounds = Aabb2::new(Point2::new(-10.0, -10.0), Point2::new(10.0, 10.0));
+    for grid in [Grid2::hashed(1.5), Grid2::uniform(bounds, 1.5).unwrap()].iter_mut() {
+        for i in 0..10 {
+            grid.insert(i, square(i as f32 * 2.0 - 10.0, 0.0));
+        }
+        assert_eq!(sorted(grid.query_bound(&Aabb2::new(Point2::new(-0.5, 0.5), Point2::new(3.5, 0.6)))),
+                   vec![5, 6]);
+        assert_eq!(sorted(grid.query_radius(Point2::new(0.5, 3.0), 2.1)), vec![5]);
+        assert_eq!(sorted(grid.query_radius(Point2::new(1.5, 3.0), 2.1)), vec![5, 6]);
+
+        let ray = Ray::new(Point2::new(-20.0, 0.5), Vector2::new(1.0, 0.0));
+        assert_eq!(grid.query_ray(&ray, 100.0), (0..10).collect::<Vec<_>>());
+        assert_eq!(grid.query_ray(&ray, 13.0), vec![0, 1]);
+        let ray = Ray::new(Point2::new(20.0, 0.5), Vector2::new(-1.0, 0.0));
+        assert_eq!(grid.query_ray(&ray, 100.0), (0..10).rev().collect::<Vec<_>>());
+        let ray = Ray::new(Point2::new(20.0, 5.5), Vector2::new(-1.0, 0.0));
+        assert_eq!(grid.query_ray(&ray, 100.0), vec![]);
+    }
+}
+
+#[test]
+fn test_grid3() {
+    let mut grid: Grid3<f32, u32> = Grid3::hashed(1.0);
+    grid.insert(0, cube(0.0, 0.0, 0.0));
+    grid.insert(1, cube(3.0, 3.0, 3.0));
+    grid.insert(2, cube(-3.0, 0.0, 0.0));
+
+    let sphere = Sphere { center: Point3::new(2.5, 2.5, 2.5), radius: 1.0 };
+    assert_eq!(grid.query_sphere(&sphere), vec![1]);
+
+    let ray = Ray::new(Point3::new(-0.5, -0.5, -0.5), Vector3::new(1.0, 1.0, 1.0));
+    assert_eq!(grid.query_ray(&ray, 10.0), vec![0, 1]);
+
+    let mut cells = Vec::new();
+    let ray = Ray::new(Point3::new(0.5, 0.5, 0.5), Vector3::new(1.0, 0.0, 0.0));
+    grid.traverse_ray(&ray, 3.0, |c, _| { cells.push(c); true });
+    assert_eq!(cells, vec![Point3::new(0, 0, 0), Point3::new(1, 0, 0),
+                           Point3::new(2, 0, 0), Point3::new(3, 0, 0)]);
+}
+
+#[test]
+fn test_degenerate() {
+    let mut grid: Grid2<f32, u32> = Grid2::hashed(1.0);
+    grid.insert(0, square(0.0, 0.0));
+    assert_eq!(grid.insert(1, square(f32::NAN, 0.0)), None);
+    assert_eq!(grid.get(&1), None);
+    assert_eq!(grid.insert(0, square(0.0, f32::NAN)), Some(square(0.0, 0.0)));
+    assert!(grid.is_empty());
+
+    assert_eq!(grid.cell(Point2::new(1e20, -1e20)), Point2::new(i32::MAX, i32::MIN));
+
+    grid.insert(0, square(0.0, 0.0));
+    let ray = Ray::new(Point2::new(0.5, 0.5), Vector2::new(0.0, 0.0));
+    assert_eq!(grid.query_ray(&ray, f32::INFINITY), vec![0]);
+    let mut visits = 0;
+    grid.traverse_ray(&ray, f32::INFINITY, |_, _| { visits += 1; visits < 10 });
+    assert_eq!(visits, 1);
+}
+
+#[test]
+fn test_limits() {
+    let bounds = Aabb2::new(Point2::new(-2.0, -2.0), Point2::new(2.0, 2.0));
+    let mut grid: Grid2<f32, u32> = Grid2::uniform(bounds, 1.0).unwrap();
+    grid.insert(0, square(5.0, 5.0));
+    let ray = Ray::new(Point2::new(10.0, 5.5), Vector2::new(-1.0, 0.0));
+    assert_eq!(grid.query_ray(&ray, 100.0), vec![0]);
+    assert_eq!(grid.query_bound(&square(8.0, 8.0)), vec![]);
+
+    let inverted = Aabb2 { min: Point2::new(0.0, 0.0), max: Point2::new(-1.0, 1.0) };
+    let infinite = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(f32::INFINITY, 1.0));
+    let huge = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(1e6, 1e6));
+    assert!(Grid2::<f32, u32>::uniform(inverted, 1.0).is_none());
+    assert!(Grid2::<f32, u32>::uniform(infinite, 1.0).is_none());
+    assert!(Grid2::<f32, u32>::uniform(huge, 1.0).is_none());
+    assert!(Grid2::<f32, u32>::uniform(bounds, 0.0).is_none());
+
+    let mut grid: Grid2<f32, u32> = Grid2::hashed(1.0);
+    grid.insert(0, square(0.0, 0.0));
+    grid.insert(1, infinite);
+    grid.insert(2, huge);
+    assert_eq!(grid.len(), 1);
+    assert_eq!(grid.query_radius(Point2::new(0.0, 0.0), f32::INFINITY), vec![0]);
+    assert_eq!(grid.query_bound(&huge), vec![0]);
+    let ray = Ray::new(Point2::new(0.5, 0.5), Vector2::new(1.0, 0.0));
+    assert_eq!(grid.query_ray(&ray, f32::INFINITY), vec![0]);
+}
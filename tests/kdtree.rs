@@ -0,0 +1,79 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+extern crate collision;
+
+use cgmath::{Point2, Point3, MetricSpace};
+use collision::{Aabb2, Aabb3, KdTree2, KdTree3};
+
+fn grid_points() -> Vec<Point2<f64>> {
+    let mut points = Vec::new();
+    for x in 0..10 {
+        for y in 0..10 {
+            points.push(Point2::new(x as f64, y as f64));
+        }
+    }
+    points
+}
+
+fn sorted(mut v: Vec<usize>) -> Vec<usize> {
+    v.sort();
+    v
+}
+
+#[test]
+fn test_empty() {
+    let tree: KdTree2<f64> = KdTree2::new(&[]);
+    assert!(tree.is_empty());
+    assert_eq!(tree.nearest(Point2::new(0.0, 0.0)), None);
+    assert_eq!(tree.within_radius(Point2::new(0.0, 0.0), 1.0), vec![]);
+}
+
+#[test]
+fn test_nearest() {
+    let points = grid_points();
+    let tree = KdTree2::new(&points);
+    assert_eq!(tree.len(), 100);
+
+    let (index, d2) = tree.nearest(Point2::new(3.25, 6.75)).unwrap();
+    assert_eq!(index, 37);
+    assert_eq!(d2, 0.125);
+    assert_eq!(tree.nearest(Point2::new(-5.0, -5.0)).map(|n| n.0), Some(0));
+
+    let p = Point2::new(4.4, 4.3);
+    let knn = tree.k_nearest(p, 5);
+    let mut brute: Vec<usize> = (0..points.len()).collect();
+    brute.sort_by(|&a, &b| points[a].distance2(p).partial_cmp(&points[b].distance2(p)).unwrap());
+    assert_eq!(knn.iter().map(|n| n.0).collect::<Vec<_>>(), &brute[..5]);
+    assert_eq!(tree.k_nearest(p, 500).len(), 100);
+}
+
+#[test]
+fn test_radius_and_range() {
+    let points = grid_points();
+    let tree = KdTree2::new(&points);
+
+    assert_eq!(sorted(tree.within_radius(Point2::new(5.0, 5.0), 1.0)), vec![45, 54, 55, 56, 65]);
+    assert_eq!(sorted(tree.range(&Aabb2::new(Point2::new(1.0, 1.0), Point2::new(2.5, 2.0)))),
+               vec![11, 12, 21, 22]);
+
+    let points3 = vec![Point3::new(0.0f32, 0.0, 0.0), Point3::new(1.0, 2.0, 3.0),
+                       Point3::new(-1.0, 5.0, 2.0), Point3::new(4.0, 4.0, 4.0)];
+    let tree3 = KdTree3::new(&points3);
+    assert_eq!(tree3.nearest(Point3::new(3.0, 3.0, 3.0)).map(|n| n.0), Some(3));
+    assert_eq!(sorted(tree3.range(&Aabb3::new(Point3::new(-1.0, 0.0, 0.0), Point3::new(1.0, 5.0, 5.0)))),
+               vec![0, 1, 2]);
+}
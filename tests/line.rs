@@ -0,0 +1,170 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+extern crate collision;
+
+use cgmath::{Point2, Point3, Vector2, Vector3};
+use collision::{Aabb2, Aabb3, Line, Line2, Line3, LineIntersection, Intersect, Plane, Ray, Sphere};
+
+#[test]
+fn test_line_line_intersection() {
+    let l0: Line2<f32> = Line::new(Point2::new(0.0, 0.0), Point2::new(4.0, 4.0));
+    let l1 = Line::new(Point2::new(0.0, 4.0), Point2::new(4.0, 0.0));
+    let l2 = Line::new(Point2::new(5.0, 0.0), Point2::new(5.0, 4.0));
+    let l3 = Line::new(Point2::new(1.0, 0.0), Point2::new(5.0, 4.0));
+
+    assert_eq!((l0, l1).intersection(), Some(LineIntersection::Point(Point2::new(2.0, 2.0))));
+    assert_eq!((l0, l2).intersection(), None);
+    assert_eq!((l0, l3).intersection(), None); // parallel
+}
+
+#[test]
+fn test_line_line_collinear() {
+    let l0: Line2<f32> = Line::new(Point2::new(0.0, 0.0), Point2::new(4.0, 0.0));
+    let l1 = Line::new(Point2::new(6.0, 0.0), Point2::new(2.0, 0.0));
+    let l2 = Line::new(Point2::new(4.0, 0.0), Point2::new(8.0, 0.0));
+    let l3 = Line::new(Point2::new(5.0, 0.0), Point2::new(8.0, 0.0));
+    let p = Line::new(Point2::new(1.0, 0.0), Point2::new(1.0, 0.0));
+
+    assert_eq!((l0, l1).intersection(),
+               Some(LineIntersection::Overlap(Line::new(Point2::new(2.0, 0.0), Point2::new(4.0, 0.0)))));
+    assert_eq!((l0, l2).intersection(), Some(LineIntersection::Point(Point2::new(4.0, 0.0))));
+    assert_eq!((l0, l3).intersection(), None);
+    assert_eq!((p, l0).intersection(), Some(LineIntersection::Point(Point2::new(1.0, 0.0))));
+    assert_eq!((l0, p).intersection(), Some(LineIntersection::Point(Point2::new(1.0, 0.0))));
+}
+
+#[test]
+fn test_line_aabb_intersection() {
+    let aabb = Aabb2::new(Point2::new(0.0f32, 0.0), Point2::new(4.0, 4.0));
+    let l0: Line2<f32> = Line::new(Point2::new(-2.0, 2.0), Point2::new(6.0, 2.0));
+    let l1 = Line::new(Point2::new(2.0, 2.0), Point2::new(6.0, 2.0));
+    let l2 = Line::new(Point2::new(-2.0, 2.0), Point2::new(-1.0, 2.0));
+    let l3 = Line::new(Point2::new(-2.0, 3.0), Point2::new(3.0, 8.0));
+
+    assert_eq!((l0, aabb).intersection(), Some(Point2::new(0.0, 2.0)));
+    assert_eq!((l1, aabb).intersection(), Some(Point2::new(2.0, 2.0)));
+    assert_eq!((l2, aabb).intersection(), None);
+    assert_eq!((l3, aabb).intersection(), None);
+}
+
+#[test]
+fn test_clip() {
+    let aabb = Aabb2::new(Point2::new(0.0f32, 0.0), Point2::new(4.0, 4.0));
+    let l0: Line2<f32> = Line::new(Point2::new(-2.0, -2.0), Point2::new(6.0, 6.0));
+    let l1 = Line::new(Point2::new(1.0, 1.0), Point2::new(2.0, 3.0));
+    let l2 = Line::new(Point2::new(-2.0, 3.0), Point2::new(3.0, 8.0));
+    let l3 = Line::new(Point2::new(2.0, 6.0), Point2::new(2.0, -2.0));
+
+    for clip in [Line2::clip_liang_barsky, Line2::clip_cohen_sutherland].iter() {
+        assert_eq!(clip(&l0, &aabb), Some(Line::new(Point2::new(0.0, 0.0), Point2::new(4.0, 4.0))));
+        assert_eq!(clip(&l1, &aabb), Some(l1));
+        assert_eq!(clip(&l2, &aabb), None);
+        assert_eq!(clip(&l3, &aabb), Some(Line::new(Point2::new(2.0, 4.0), Point2::new(2.0, 0.0))));
+    }
+}
+
+#[test]
+fn test_line3_geometry() {
+    let l: Line3<f32> = Line::new(Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 0.0, 4.0));
+    assert_eq!(l.length(), 4.0);
+    assert_eq!(l.direction(), Vector3::new(0.0, 0.0, 1.0));
+    assert_eq!(l.point_at(0.25), Point3::new(1.0, 0.0, 1.0));
+    assert_eq!(l.closest_param(Point3::new(5.0, 5.0, 2.0)), 0.5);
+    assert_eq!(l.closest_param(Point3::new(0.0, 0.0, -3.0)), 0.0);
+    assert_eq!(l.closest_point(Point3::new(0.0, 0.0, 9.0)), Point3::new(1.0, 0.0, 4.0));
+
+    let (ray, t) = l.to_ray();
+    assert_eq!(ray.direction, Vector3::new(0.0, 0.0, 1.0));
+    assert_eq!(t, 4.0);
+    assert_eq!(ray.to_line(0.0, t), l);
+}
+
+#[test]
+fn test_line3_plane() {
+    let plane = Plane::from_point_normal(Point3::new(0.0f32, 0.0, 2.0), Vector3::new(0.0, 0.0, 1.0));
+    let l0: Line3<f32> = Line::new(Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 4.0));
+    let l1: Line3<f32> = Line::new(Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 1.0));
+
+    assert_eq!((plane, l0).intersection(), Some(Point3::new(0.0, 0.0, 2.0)));
+    assert_eq!((plane, l1).intersection(), None);
+
+    let mid = Point3::new(0.0, 0.0, 2.0);
+    assert_eq!(l0.split_plane(&plane),
+               (Some(Line::new(mid, l0.dest)), Some(Line::new(l0.origin, mid))));
+    assert_eq!(l1.split_plane(&plane), (None, Some(l1)));
+}
+
+#[test]
+fn test_line3_aabb_sphere() {
+    let aabb = Aabb3::new(Point3::new(-1.0f32, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+    let sphere = Sphere { center: Point3::new(0.0f32, 0.0, 0.0), radius: 1.0 };
+    let l0: Line3<f32> = Line::new(Point3::new(-3.0, 0.0, 0.0), Point3::new(3.0, 0.0, 0.0));
+    let l1: Line3<f32> = Line::new(Point3::new(-3.0, 0.0, 0.0), Point3::new(-2.0, 0.0, 0.0));
+    let l2: Line3<f32> = Line::new(Point3::new(0.0, 0.0, 0.0), Point3::new(3.0, 0.0, 0.0));
+
+    assert_eq!((l0, aabb).intersection(), Some(Point3::new(-1.0, 0.0, 0.0)));
+    assert_eq!((l1, aabb).intersection(), None);
+    assert_eq!((l2, aabb).intersection(), Some(Point3::new(0.0, 0.0, 0.0)));
+
+    assert_eq!((sphere, l0).intersection(), Some(Point3::new(-1.0, 0.0, 0.0)));
+    assert_eq!((sphere, l1).intersection(), None);
+    assert_eq!((sphere, l2).intersection(), Some(Point3::new(0.0, 0.0, 0.0)));
+}
+
+#[test]
+fn test_line3_triangle() {
+    let a = Point3::new(0.0f32, 0.0, 0.0);
+    let b = Point3::new(4.0f32, 0.0, 0.0);
+    let c = Point3::new(0.0f32, 4.0, 0.0);
+    let l0: Line3<f32> = Line::new(Point3::new(1.0, 1.0, 2.0), Point3::new(1.0, 1.0, -2.0));
+    let l1: Line3<f32> = Line::new(Point3::new(1.0, 1.0, 2.0), Point3::new(1.0, 1.0, 1.0));
+    let l2: Line3<f32> = Line::new(Point3::new(3.0, 3.0, 2.0), Point3::new(3.0, 3.0, -2.0));
+
+    assert_eq!(l0.intersect_triangle(a, b, c), Some(Point3::new(1.0, 1.0, 0.0)));
+    assert_eq!(l1.intersect_triangle(a, b, c), None);
+    assert_eq!(l2.intersect_triangle(a, b, c), None);
+}
+
+#[test]
+fn test_intersection_degenerate() {
+    let nan = std::f32::NAN;
+    let line = Line::new(Point2::new(0.0f32, 0.0), Point2::new(4.0, 0.0));
+
+    // a ray with a zero direction only hits segments through its origin
+    let ray = Ray::new(Point2::new(2.0f32, 0.0), Vector2::new(0.0, 0.0));
+    assert_eq!((ray, line).intersection(), Some(Point2::new(2.0, 0.0)));
+    let ray = Ray::new(Point2::new(2.0f32, 1.0), Vector2::new(0.0, 0.0));
+    assert_eq!((ray, line).intersection(), None);
+    let ray = Ray::new(Point2::new(6.0f32, 0.0), Vector2::new(0.0, 0.0));
+    assert_eq!((ray, line).intersection(), None);
+
+    // parallel and collinear rays
+    let ray = Ray::new(Point2::new(-2.0f32, 1.0), Vector2::new(1.0, 0.0));
+    assert_eq!((ray, line).intersection(), None);
+    let ray = Ray::new(Point2::new(-2.0f32, 0.0), Vector2::new(1.0, 0.0));
+    assert_eq!((ray, line).intersection(), Some(Point2::new(0.0, 0.0)));
+
+    // NaN anywhere
+    let ray = Ray::new(Point2::new(2.0f32, -1.0), Vector2::new(nan, 1.0));
+    assert_eq!((ray, line).intersection(), None);
+    let bad = Line::new(Point2::new(nan, 0.0f32), Point2::new(4.0, 0.0));
+    let ray = Ray::new(Point2::new(2.0f32, -1.0), Vector2::new(0.0, 1.0));
+    assert_eq!((ray, bad).intersection(), None);
+    assert_eq!((bad, line).intersection(), None);
+    assert_eq!((line, bad).intersection(), None);
+    assert_eq!(bad.clip_liang_barsky(&Aabb2::new(Point2::new(-1.0, -1.0), Point2::new(5.0, 1.0))), None);
+}
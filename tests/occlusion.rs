@@ -0,0 +1,85 @@
+// Copyright 2015 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+extern crate collision;
+extern crate cgmath;
+
+use cgmath::{Matrix4, PerspectiveFov, Point3, Rad};
+use collision::{Aabb3, DepthRange, OcclusionBuffer, Relation, Sphere};
+
+fn buffer(mat: Matrix4<f32>, depth: DepthRange) -> OcclusionBuffer<f32> {
+    let mut buffer = OcclusionBuffer::new(64, 48, mat, depth);
+    // a wall covering the middle of the screen
+    buffer.add_aabb(&Aabb3::new(Point3::new(-1.0, -1.0, -6.0), Point3::new(1.0, 1.0, -5.0)));
+    buffer
+}
+
+fn check(buffer: &OcclusionBuffer<f32>) {
+    let aabb = |x: f32, z: f32, size: f32| {
+        Aabb3::new(Point3::new(x - size, -size, z - size), Point3::new(x + size, size, z + size))
+    };
+    assert_eq!(buffer.test_aabb(&aabb(0.0, -20.0, 1.0)), Relation::Out);
+    assert_eq!(buffer.test_aabb(&aabb(0.0, -3.0, 0.5)), Relation::In);
+    assert_eq!(buffer.test_aabb(&aabb(10.0, -20.0, 1.0)), Relation::In);
+    assert_eq!(buffer.test_aabb(&aabb(0.0, -20.0, 6.0)), Relation::In);
+    assert_eq!(buffer.test_aabb(&aabb(0.0, 0.0, 2.0)), Relation::Cross);
+    assert_eq!(buffer.test_aabb(&aabb(100.0, -20.0, 1.0)), Relation::Out);
+
+    let sphere = |z: f32, radius: f32| Sphere { center: Point3::new(0.0, 0.0, z), radius: radius };
+    assert_eq!(buffer.test_sphere(&sphere(-20.0, 1.0)), Relation::Out);
+    assert_eq!(buffer.test_sphere(&sphere(-20.0, 6.0)), Relation::In);
+}
+
+#[test]
+fn test_occlusion() {
+    let mat: Matrix4<f32> = PerspectiveFov {
+        fovy: Rad(std::f32::consts::FRAC_PI_2),
+        aspect: 4.0 / 3.0,
+        near: 1.0,
+        far: 100.0,
+    }.into();
+    let mut buffer = buffer(mat, DepthRange::NegativeOneToOne);
+    assert_eq!(buffer.width(), 64);
+    assert_eq!(buffer.height(), 48);
+    assert!(buffer.depth(32, 24) < 1.0);
+    assert_eq!(buffer.depth(0, 0), 1.0);
+
+    check(&buffer);
+    buffer.build();
+    check(&buffer);
+
+    buffer.clear(mat);
+    assert_eq!(buffer.test_aabb(&Aabb3::new(Point3::new(-1.0, -1.0, -21.0), Point3::new(1.0, 1.0, -19.0))), Relation::In);
+}
+
+#[test]
+fn test_reversed() {
+    let (n, f) = (1.0, 100.0);
+    let mut mat: Matrix4<f32> = PerspectiveFov {
+        fovy: Rad(std::f32::consts::FRAC_PI_2),
+        aspect: 4.0 / 3.0,
+        near: n,
+        far: f,
+    }.into();
+    mat.z.z = n / (f - n);
+    mat.w.z = f * n / (f - n);
+
+    let mut buffer = buffer(mat, DepthRange::ReversedZeroToOne);
+    assert!(buffer.depth(32, 24) > 0.0);
+    assert_eq!(buffer.depth(0, 0), 0.0);
+    buffer.build();
+    check(&buffer);
+}
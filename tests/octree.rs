@@ -0,0 +1,103 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+extern crate collision;
+
+use std::f32;
+
+use cgmath::{Point2, Point3, Vector2, Vector3, PerspectiveFov, Quaternion, Rad, Rotation3};
+use collision::{Aabb2, Aabb3, Frustum, Quadtree, Octree, Ray, Sphere, Projection};
+
+fn square(x: f32, y: f32, size: f32) -> Aabb2<f32> {
+    Aabb2::new(Point2::new(x, y), Point2::new(x + size, y + size))
+}
+
+fn cube(x: f32, y: f32, z: f32) -> Aabb3<f32> {
+    Aabb3::new(Point3::new(x, y, z), Point3::new(x + 1.0, y + 1.0, z + 1.0))
+}
+
+fn sorted(mut v: Vec<u32>) -> Vec<u32> {
+    v.sort();
+    v
+}
+
+#[test]
+fn test_quadtree() {
+    let bounds = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(64.0, 64.0));
+    let mut tree: Quadtree<f32, u32> = Quadtree::new(bounds, 5, 2.0);
+    for i in 0..8 {
+        tree.insert(i, square(i as f32 * 8.0, i as f32 * 8.0, 1.0));
+    }
+    tree.insert(100, square(-10.0, -10.0, 100.0));
+    tree.insert(200, square(70.0, 70.0, 1.0));
+    assert_eq!(tree.len(), 10);
+
+    assert_eq!(sorted(tree.query_bound(&square(15.0, 15.0, 2.0))), vec![2, 100]);
+    assert_eq!(sorted(tree.query_radius(Point2::new(20.0, 20.0), 5.0)), vec![2, 100]);
+    assert_eq!(sorted(tree.query_bound(&square(69.0, 69.0, 2.0))), vec![100, 200]);
+
+    assert!(tree.update(2, square(40.5, 40.5, 1.0)));
+    assert_eq!(sorted(tree.query_bound(&square(15.0, 15.0, 2.0))), vec![100]);
+    assert_eq!(sorted(tree.query_bound(&square(40.0, 40.0, 1.0))), vec![2, 5, 100]);
+
+    assert_eq!(tree.remove(&100), Some(square(-10.0, -10.0, 100.0)));
+    assert_eq!(sorted(tree.query_bound(&square(15.0, 15.0, 2.0))), vec![]);
+    assert_eq!(tree.len(), 9);
+}
+
+#[test]
+fn test_quadtree_ray() {
+    let bounds = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(64.0, 64.0));
+    let mut tree: Quadtree<f32, u32> = Quadtree::new(bounds, 5, 2.0);
+    for i in 0..8 {
+        tree.insert(i, square(i as f32 * 8.0, 4.0, 1.0));
+    }
+    let ray = Ray::new(Point2::new(64.0, 4.5), Vector2::new(-1.0, 0.0));
+    let hits: Vec<u32> = tree.query_ray(&ray).into_iter().map(|(h, _)| h).collect();
+    assert_eq!(hits, vec![7, 6, 5, 4, 3, 2, 1, 0]);
+    assert_eq!(tree.query_ray(&ray)[0], (7, 7.0));
+}
+
+#[test]
+fn test_octree() {
+    let bounds = Aabb3::new(Point3::new(-32.0, -32.0, -32.0), Point3::new(32.0, 32.0, 32.0));
+    let mut tree: Octree<f32, u32> = Octree::new(bounds, 4, 2.0);
+    tree.insert(0, cube(0.0, 0.0, -5.0));
+    tree.insert(1, cube(0.0, 20.0, -5.0));
+    tree.insert(2, cube(0.0, 0.0, 5.0));
+    tree.insert(3, cube(-20.0, -20.0, -20.0));
+
+    let sphere = Sphere { center: Point3::new(0.0, 0.0, 0.0), radius: 5.0 };
+    assert_eq!(sorted(tree.query_sphere(&sphere)), vec![0, 2]);
+
+    let frustum = PerspectiveFov {
+        fovy: Rad(1f32),
+        aspect: 1f32,
+        near: 1f32,
+        far: 10f32,
+    }.to_frustum();
+    assert_eq!(tree.cull(&frustum), vec![0]);
+
+    // the planes remembered from one view do not affect the next
+    let back = Frustum::from_camera(Point3::new(0.5, 0.5, 0.0), Quaternion::from_angle_y(Rad(f32::consts::PI)),
+                                    Rad(1.0), 1.0, 1.0, 10.0).unwrap();
+    assert_eq!(tree.cull(&back), vec![2]);
+    assert_eq!(tree.cull(&frustum), vec![0]);
+    assert_eq!(tree.cull(&back), vec![2]);
+
+    let ray = Ray::new(Point3::new(0.5, 0.5, 20.0), Vector3::new(0.0, 0.0, -1.0));
+    assert_eq!(tree.query_ray(&ray), vec![(2, 14.0), (0, 24.0)]);
+}
@@ -0,0 +1,221 @@
+// Copyright 2015 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+
+extern crate cgmath;
+extern crate collision;
+
+use std::f64::consts::PI;
+
+use cgmath::*;
+use collision::*;
+
+/// One of each bound around `(x, 0, 0)`, all overlapping each other.
+struct Scene {
+    aabb: Aabb3<f64>,
+    sphere: Sphere<f64>,
+    plane: Plane<f64>,
+    frustum: Frustum<f64>,
+    obb: Obb3<f64>,
+    cylinder: Cylinder<f64>,
+    line: Line3<f64>,
+    ray: Ray3<f64>,
+}
+
+fn scene(x: f64) -> Scene {
+    let side = |n: Vector3<f64>, d: f64| Plane::new(n, d + n.x * x);
+    Scene {
+        aabb: Aabb3::new(Point3::new(x - 1.0, -1.0, -1.0), Point3::new(x + 1.0, 1.0, 1.0)),
+        sphere: Sphere { center: Point3::new(x, 0.0, 0.5), radius: 1.0 },
+        plane: Plane::new(Vector3::unit_x(), x),
+        frustum: Frustum::new(side(Vector3::unit_x(), -2.0), side(-Vector3::unit_x(), -2.0),
+                              side(Vector3::unit_y(), -2.0), side(-Vector3::unit_y(), -2.0),
+                              side(Vector3::unit_z(), -2.0), side(-Vector3::unit_z(), -2.0)),
+        obb: Obb3::new(Point3::new(x, 0.0, 0.0), Vector3::new(0.0, 0.0, PI / 4.0),
+                       Vector3::new(1.5, 0.2, 0.2)),
+        cylinder: Cylinder::new(Point3::new(x, 0.0, 0.0), Vector3::unit_z(), 0.5),
+        line: Line::new(Point3::new(x - 2.0, 0.1, 0.0), Point3::new(x + 2.0, 0.1, 0.0)),
+        ray: Ray::new(Point3::new(x, -5.0, 0.0), Vector3::unit_y()),
+    }
+}
+
+/// Check a pair in both orders.
+fn check<A: Overlap<B>, B: Overlap<A>>(a: &A, b: &B, expected: bool) {
+    assert_eq!(a.overlaps(b), expected);
+    assert_eq!(b.overlaps(a), expected);
+}
+
+/// Check a bound against every bound of the scene around the origin, and
+/// of a scene far away from it.
+macro_rules! check_row {
+    ($field:ident) => {
+        for &(x, expected) in [(0.0, true), (10.0, false)].iter() {
+            let (a, b) = (scene(0.0), scene(x));
+            check(&a.$field, &b.aabb, expected);
+            check(&a.$field, &b.sphere, expected);
+            check(&a.$field, &b.plane, expected);
+            check(&a.$field, &b.frustum, expected);
+            check(&a.$field, &b.obb, expected);
+            check(&a.$field, &b.cylinder, expected);
+            check(&a.$field, &b.line, expected);
+            check(&a.$field, &b.ray, expected);
+        }
+    }
+}
+
+#[test]
+fn test_aabb3() {
+    check_row!(aabb);
+    let aabb = scene(0.0).aabb;
+    check(&aabb, &Aabb3::new(Point3::new(1.0, 1.0, 1.0), Point3::new(2.0, 2.0, 2.0)), true);
+    check(&aabb, &Sphere { center: Point3::new(2.0, 2.0, 2.0), radius: 1.5 }, false);
+    check(&aabb, &Plane::new(Vector3::new(1.0, 1.0, 1.0).normalize(), 1.8), false);
+    check(&aabb, &Obb3::new(Point3::new(2.2, 0.0, 0.0), Vector3::new(0.0, 0.0, PI / 4.0),
+                            Vector3::new(1.0, 1.0, 1.0)), true);
+    check(&aabb, &Cylinder::new(Point3::new(2.0, 2.0, 0.0), Vector3::unit_z(), 1.3), false);
+}
+
+#[test]
+fn test_sphere() {
+    check_row!(sphere);
+    let sphere = Sphere { center: Point3::new(0.0, 0.0, 0.0), radius: 1.0 };
+    check(&sphere, &Sphere { center: Point3::new(2.0, 0.0, 0.0), radius: 1.0 }, true);
+    check(&sphere, &Obb3::new(Point3::new(1.5, 1.5, 0.0), Vector3::zero(),
+                              Vector3::new(0.5, 0.5, 0.5)), false);
+    check(&sphere, &Cylinder::new(Point3::new(1.5, 0.0, 2.5), Vector3::unit_z(), 1.0), false);
+    check(&sphere, &Line::new(Point3::new(-2.0, 1.5, 0.0), Point3::new(2.0, 1.5, 0.0)), false);
+}
+
+#[test]
+fn test_plane() {
+    check_row!(plane);
+    let plane = Plane::new(Vector3::unit_x(), 0.0);
+    check(&plane, &Plane::new(-Vector3::unit_x(), 0.0), true);
+    check(&plane, &Plane::new(Vector3::unit_y(), 5.0), true);
+    check(&plane, &Cylinder::new(Point3::new(1.0, 0.0, 0.0), Vector3::unit_z(), 1.0), true);
+    check(&plane, &Ray::new(Point3::new(1.0, 0.0, 0.0), Vector3::unit_x()), false);
+}
+
+#[test]
+fn test_frustum() {
+    check_row!(frustum);
+    // looking down -z from the origin, with the near plane at z = -1
+    let frustum = Frustum::new_infinite(
+        Plane::new(Vector3::new(1.0, 0.0, -1.0).normalize(), 0.0),
+        Plane::new(Vector3::new(-1.0, 0.0, -1.0).normalize(), 0.0),
+        Plane::new(Vector3::new(0.0, 1.0, -1.0).normalize(), 0.0),
+        Plane::new(Vector3::new(0.0, -1.0, -1.0).normalize(), 0.0),
+        Plane::new(-Vector3::unit_z(), 1.0));
+    check(&frustum, &Sphere { center: Point3::new(0.0, 0.0, -100.0), radius: 1.0 }, true);
+    check(&frustum, &Sphere { center: Point3::new(0.0, 0.0, 1.0), radius: 1.0 }, false);
+    check(&frustum, &Sphere { center: Point3::new(-6.0, -6.0, -3.0), radius: 2.3 }, false);
+    check(&frustum, &Cylinder::new(Point3::new(50.0, 0.0, -60.0), Vector3::unit_x(), 1.0), true);
+    check(&frustum, &Plane::new(Vector3::unit_z(), -1000.0), true);
+    check(&frustum, &Plane::new(Vector3::unit_z(), 0.0), false);
+    check(&frustum, &Ray::new(Point3::new(0.0, 0.0, 0.0), -Vector3::unit_z()), true);
+}
+
+#[test]
+fn test_obb3() {
+    check_row!(obb);
+    let obb = scene(0.0).obb;
+    check(&obb, &Obb3::new(Point3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, -PI / 4.0),
+                           Vector3::new(1.5, 0.2, 0.2)), true);
+    check(&obb, &Ray::new(Point3::new(-1.0, 1.0, 0.0), Vector3::unit_x()), true);
+    check(&obb, &Ray::new(Point3::new(-1.0, 1.0, 0.0), -Vector3::unit_x()), false);
+    check(&obb, &Line::new(Point3::new(1.0, -1.0, -1.0), Point3::new(1.0, -1.0, 1.0)), false);
+}
+
+#[test]
+fn test_cylinder() {
+    check_row!(cylinder);
+    let cylinder = scene(0.0).cylinder;
+    check(&cylinder, &Cylinder::new(Point3::new(0.0, 0.0, 2.0), Vector3::unit_x(), 1.0), true);
+    check(&cylinder, &Cylinder::new(Point3::new(0.0, 0.0, 2.5), Vector3::unit_x(), 1.0), false);
+    check(&cylinder, &Ray::new(Point3::new(0.0, 0.0, -3.0), Vector3::unit_z()), true);
+    check(&cylinder, &Line::new(Point3::new(0.4, 0.4, 0.0), Point3::new(2.0, 2.0, 0.0)), false);
+}
+
+#[test]
+fn test_line() {
+    check_row!(line);
+    let line = Line::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+    check(&line, &Line::new(Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 1.0)), true);
+    check(&line, &Line::new(Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)), false);
+    check(&line, &Ray::new(Point3::new(2.0, 2.0, 2.0), Vector3::new(-1.0, -1.0, -1.0)), true);
+    check(&line, &Ray::new(Point3::new(2.0, 2.0, 2.0), Vector3::new(1.0, 1.0, 1.0)), false);
+}
+
+#[test]
+fn test_ray() {
+    check_row!(ray);
+    let ray = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::unit_x());
+    check(&ray, &Ray::new(Point3::new(5.0, 5.0, 0.0), -Vector3::unit_y()), true);
+    check(&ray, &Ray::new(Point3::new(5.0, 5.0, 0.0), Vector3::unit_y()), false);
+    check(&ray, &Ray::new(Point3::new(-5.0, 5.0, 0.0), -Vector3::unit_y()), false);
+}
+
+#[test]
+fn test_aabb2() {
+    let a = Aabb2::new(Point2::new(0, 0), Point2::new(2, 2));
+    check(&a, &Aabb2::new(Point2::new(2, 1), Point2::new(3, 3)), true);
+    check(&a, &Aabb2::new(Point2::new(3, 0), Point2::new(4, 2)), false);
+}
+
+#[test]
+fn test_region() {
+    let a = Aabb3::new(Point3::new(0, 0, 0), Point3::new(2, 2, 2));
+    let b = Aabb3::new(Point3::new(1, -1, 2), Point3::new(3, 1, 4));
+    let c = Aabb3::new(Point3::new(1, 3, 1), Point3::new(3, 4, 4));
+    assert_eq!((a, b).intersection(), Some(Aabb3::new(Point3::new(1, 0, 2), Point3::new(2, 1, 2))));
+    assert_eq!((a, c).intersection(), None);
+
+    let a = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(2.0, 2.0));
+    let b = Aabb2::new(Point2::new(1.0, -1.0), Point2::new(3.0, 1.0));
+    assert_eq!((a, b).intersection(), Some(Aabb2::new(Point2::new(1.0, 0.0), Point2::new(2.0, 1.0))));
+}
+
+#[test]
+fn test_penetration() {
+    let a = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(2.0, 2.0));
+    let b = Aabb2::new(Point2::new(1.0, 1.5), Point2::new(3.0, 3.0));
+    assert_eq!(a.penetration(&b), Some(Vector2::new(0.0, -0.5)));
+    assert_eq!(b.penetration(&a), Some(Vector2::new(0.0, 0.5)));
+
+    let a = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 2.0, 2.0));
+    let b = Aabb3::new(Point3::new(1.5, 0.0, 0.0), Point3::new(3.0, 2.0, 2.0));
+    assert_eq!(a.penetration(&b), Some(Vector3::new(-0.5, 0.0, 0.0)));
+    assert_eq!(a.penetration(&Aabb3::new(Point3::new(3.0, 0.0, 0.0), Point3::new(4.0, 1.0, 1.0))), None);
+
+    let s = Sphere { center: Point3::new(0.0, 0.0, 0.0), radius: 1.0 };
+    let t = Sphere { center: Point3::new(0.0, 1.5, 0.0), radius: 1.0 };
+    assert_ulps_eq!(s.penetration(&t).unwrap(), &Vector3::new(0.0, -0.5, 0.0));
+    assert_eq!(s.penetration(&Sphere { center: Point3::new(3.0, 0.0, 0.0), radius: 1.0 }), None);
+
+    let s = Sphere { center: Point3::new(2.5, 1.0, 1.0), radius: 1.0 };
+    assert_ulps_eq!(s.penetration(&a).unwrap(), &Vector3::new(0.5, 0.0, 0.0));
+    assert_ulps_eq!(a.penetration(&s).unwrap(), &Vector3::new(-0.5, 0.0, 0.0));
+    let s = Sphere { center: Point3::new(1.0, 1.0, 1.8), radius: 0.5 };
+    assert_ulps_eq!(s.penetration(&a).unwrap(), &Vector3::new(0.0, 0.0, 0.7));
+    let s = Sphere { center: Point3::new(1.0, 1.0, 1.0), radius: -0.5 };
+    assert_eq!(s.penetration(&a), None);
+    assert_eq!(a.penetration(&s), None);
+
+    let p = Plane::new(Vector3::unit_z(), 1.0);
+    let s = Sphere { center: Point3::new(0.0, 0.0, 0.5), radius: 1.0 };
+    assert_ulps_eq!(s.penetration(&p).unwrap(), &Vector3::new(0.0, 0.0, -0.5));
+    assert_ulps_eq!(p.penetration(&s).unwrap(), &Vector3::new(0.0, 0.0, 0.5));
+}
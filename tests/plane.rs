@@ -28,7 +28,7 @@ fn test_from_points() {
     assert_eq!(Plane::from_points(Point3::new(5.0f64, 0.0f64,  5.0f64),
                                   Point3::new(5.0f64, 5.0f64,  5.0f64),
                                   Point3::new(5.0f64, 0.0f64, -1.0f64)),
-    	Some(Plane::from_abcd(-1.0f64, 0.0f64, 0.0f64, 5.0f64)));
+        Some(Plane::from_abcd(-1.0f64, 0.0f64, 0.0f64, -5.0f64)));
 
     assert_eq!(Plane::from_points(Point3::new(0.0f64, 5.0f64, -5.0f64),
                                   Point3::new(0.0f64, 5.0f64,  0.0f64),
@@ -38,7 +38,7 @@ fn test_from_points() {
 
 #[test]
 fn test_ray_intersection() {
-    let p0 = Plane::from_abcd(1f64, 0f64, 0f64, -7f64);
+    let p0 = Plane::from_abcd(1f64, 0f64, 0f64, 7f64);
     let r0: Ray3<f64> = Ray::new(Point3::new(2f64, 3f64, 4f64), Vector3::new(1f64, 1f64, 1f64).normalize());
     assert_eq!((p0, r0).intersection(), Some(Point3::new(7f64, 8f64, 9f64)));
 
@@ -89,3 +89,156 @@ fn test_plane3_intersection() {
     let point = (p0, p1, p2).intersection();
     assert!(point.is_none());
 }
+
+#[test]
+fn test_distance() {
+    let p = Plane::from_point_normal(Point3::new(0.0f64, 2.0, 0.0), Vector3::unit_y());
+    assert_ulps_eq!(p.distance(Point3::new(5.0, 7.0, 1.0)), &5.0);
+    assert_ulps_eq!(p.distance(Point3::new(5.0, -1.0, 1.0)), &-3.0);
+    assert_ulps_eq!(p.flip().distance(Point3::new(5.0, -1.0, 1.0)), &3.0);
+    assert_eq!(p.flip().flip(), p);
+
+    assert_ulps_eq!(p.project_point(Point3::new(5.0, 7.0, 1.0)), &Point3::new(5.0, 2.0, 1.0));
+    assert_ulps_eq!(p.reflect_point(Point3::new(5.0, 7.0, 1.0)), &Point3::new(5.0, -3.0, 1.0));
+    assert_ulps_eq!(p.reflect_vector(Vector3::new(1.0, -1.0, 0.0)), &Vector3::new(1.0, 1.0, 0.0));
+
+    // an unnormalized normal gives the same points
+    let q = Plane::new(Vector3::new(0.0f64, 2.0, 0.0), 4.0);
+    assert_ulps_eq!(q.project_point(Point3::new(5.0, 7.0, 1.0)), &Point3::new(5.0, 2.0, 1.0));
+    assert_ulps_eq!(q.reflect_point(Point3::new(5.0, 7.0, 1.0)), &Point3::new(5.0, -3.0, 1.0));
+}
+
+#[test]
+fn test_reflect_ray() {
+    let p = Plane::from_point_normal(Point3::new(0.0f64, 2.0, 0.0), Vector3::unit_y());
+    let r = Ray3::new(Point3::new(0.0f64, 5.0, 0.0), Vector3::new(1.0, -1.0, 0.0).normalize());
+    let reflected = p.reflect_ray(&r);
+    assert_ulps_eq!(reflected.origin, &Point3::new(0.0, -1.0, 0.0));
+    assert_ulps_eq!(reflected.direction, &Vector3::new(1.0, 1.0, 0.0).normalize());
+}
+
+#[test]
+fn test_transform() {
+    let p = Plane::from_point_normal(Point3::new(1.0f64, 0.0, 0.0), Vector3::unit_x());
+    let moved = p.transform(Matrix4::from_translation(Vector3::new(2.0, 5.0, 0.0))).unwrap();
+    assert_ulps_eq!(moved, &Plane::new(Vector3::unit_x(), 3.0));
+
+    let turned = p.transform(Matrix4::from_angle_z(Rad(std::f64::consts::FRAC_PI_2))).unwrap();
+    assert_relative_eq!(turned, &Plane::new(Vector3::unit_y(), 1.0), epsilon = 1e-12);
+
+    let scaled = p.transform(Matrix4::from_scale(2.0)).unwrap();
+    assert_ulps_eq!(scaled, &Plane::new(Vector3::unit_x(), 2.0));
+
+    assert_eq!(p.transform(Matrix4::from_scale(0.0)), None);
+}
+
+#[test]
+fn test_line_intersection() {
+    let p = Plane::from_point_normal(Point3::new(0.0f64, 2.0, 0.0), Vector3::unit_y());
+    let l = Line3::new(Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 4.0, 0.0));
+    assert_eq!((p, l).intersection(), Some(Point3::new(1.0, 2.0, 0.0)));
+    let l = Line3::new(Point3::new(1.0, 3.0, 0.0), Point3::new(1.0, 4.0, 0.0));
+    assert_eq!((p, l).intersection(), None);
+}
+
+#[test]
+fn test_conventions() {
+    let p = Plane::from_abcd(0.0f64, 1.0, 0.0, 2.0);
+    assert_eq!(Plane::from_abcd_alt(0.0, 1.0, 0.0, -2.0), p);
+    assert_eq!(Plane::from_vector4(p.to_vector4()), p);
+    assert_eq!(Plane::from_vector4_alt(p.to_vector4_alt()), p);
+    assert_eq!(p.to_vector4_alt(), Vector4::new(0.0, 1.0, 0.0, -2.0));
+
+    let q = Point3::new(3.0, 5.0, -1.0);
+    assert_ulps_eq!(p.to_vector4_alt().dot(q.to_homogeneous()), &p.distance(q));
+    assert_ulps_eq!(p.to_vector4().truncate().dot(q.to_vec()) - p.to_vector4().w, &p.distance(q));
+}
+
+#[test]
+fn test_from_points_round_trip() {
+    let triangles = [
+        (Point3::new(1.0f64, 2.0, 3.0), Point3::new(4.0, -1.0, 2.0), Point3::new(0.0, 5.0, -3.0)),
+        (Point3::new(5.0f64, 0.0, 5.0), Point3::new(5.0, 5.0, 5.0), Point3::new(5.0, 0.0, -1.0)),
+        (Point3::new(-2.0f64, 7.0, 1.0), Point3::new(3.0, 7.0, 1.0), Point3::new(3.0, 7.0, -4.0)),
+    ];
+    for &(a, b, c) in triangles.iter() {
+        let p = Plane::from_points(a, b, c).unwrap();
+        for q in [a, b, c].iter() {
+            assert_relative_eq!(p.distance(*q), &0.0, epsilon = 1e-12);
+        }
+        assert_relative_eq!(p.n, &(b - a).cross(c - a).normalize(), epsilon = 1e-12);
+        assert_relative_eq!(Plane::from_point_normal(a, p.n), &p, epsilon = 1e-12);
+
+        // the normal side is inside for every bound
+        let front = a + p.n * 2.0;
+        let back = a + p.n * -2.0;
+        assert_eq!(front.relate_plane(p), Relation::In);
+        assert_eq!(back.relate_plane(p), Relation::Out);
+        assert_eq!(Sphere { center: front, radius: 1.0 }.relate_plane(p), Relation::In);
+        assert_eq!(Sphere { center: back, radius: 1.0 }.relate_plane(p), Relation::Out);
+        assert_eq!(Sphere { center: a, radius: 1.0 }.relate_plane(p), Relation::Cross);
+        let unit = Vector3::new(0.5, 0.5, 0.5);
+        assert_eq!(Aabb3::new(front + (-unit), front + unit).relate_plane(p), Relation::In);
+        assert_eq!(Aabb3::new(back + (-unit), back + unit).relate_plane(p), Relation::Out);
+        assert_eq!(Aabb3::new(a + (-unit), a + unit).relate_plane(p), Relation::Cross);
+
+        // rays and segments hit the plane on it
+        let r = Ray3::new(front, -p.n);
+        let hit = (p, r).intersection().unwrap();
+        assert_relative_eq!(p.distance(hit), &0.0, epsilon = 1e-12);
+        assert_relative_eq!(hit, &a, epsilon = 1e-12);
+        let hit = (p, Line3::new(front, back)).intersection().unwrap();
+        assert_relative_eq!(hit, &a, epsilon = 1e-12);
+        assert_eq!((p, Ray3::new(front, p.n)).intersection(), None);
+
+        let turned = p.transform(Matrix4::from_angle_x(Rad(1.0))).unwrap();
+        let moved = Matrix4::from_angle_x(Rad(1.0)).transform_point(a);
+        assert_relative_eq!(turned.distance(moved), &0.0, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_intersection_degenerate() {
+    let nan = std::f64::NAN;
+    let p = Plane::from_point_normal(Point3::new(0f64, 2f64, 0f64), Vector3::unit_y());
+
+    // parallel rays only hit the plane they lie on
+    let r = Ray3::new(Point3::new(0f64, 3f64, 0f64), Vector3::unit_x());
+    assert_eq!((p, r).intersection(), None);
+    let r = Ray3::new(Point3::new(1f64, 2f64, 0f64), Vector3::unit_x());
+    assert_eq!((p, r).intersection(), Some(Point3::new(1f64, 2f64, 0f64)));
+
+    // zero directions are points
+    let r = Ray3::new(Point3::new(1f64, 2f64, 0f64), Vector3::zero());
+    assert_eq!((p, r).intersection(), Some(Point3::new(1f64, 2f64, 0f64)));
+    let r = Ray3::new(Point3::new(1f64, 3f64, 0f64), Vector3::zero());
+    assert_eq!((p, r).intersection(), None);
+
+    // starting on the plane
+    let r = Ray3::new(Point3::new(1f64, 2f64, 0f64), Vector3::unit_y());
+    assert_eq!((p, r).intersection(), Some(Point3::new(1f64, 2f64, 0f64)));
+
+    // parallel and zero length segments
+    let l = Line3::new(Point3::new(0f64, 2f64, 0f64), Point3::new(5f64, 2f64, 0f64));
+    assert_eq!((p, l).intersection(), Some(Point3::new(0f64, 2f64, 0f64)));
+    let l = Line3::new(Point3::new(0f64, 3f64, 0f64), Point3::new(5f64, 3f64, 0f64));
+    assert_eq!((p, l).intersection(), None);
+    let l = Line3::new(Point3::new(1f64, 2f64, 0f64), Point3::new(1f64, 2f64, 0f64));
+    assert_eq!((p, l).intersection(), Some(Point3::new(1f64, 2f64, 0f64)));
+
+    // zero normals
+    let zero = Plane::new(Vector3::zero(), 0f64);
+    assert!((zero, Plane::new(Vector3::unit_x(), 1f64)).intersection().is_none());
+    assert_eq!((zero, p, Plane::new(Vector3::unit_x(), 1f64)).intersection(), None);
+
+    // NaN anywhere
+    let r = Ray3::new(Point3::new(0f64, nan, 0f64), Vector3::unit_y());
+    assert_eq!((p, r).intersection(), None);
+    let r = Ray3::new(Point3::new(0f64, 0f64, 0f64), Vector3::new(0f64, 1f64, nan));
+    assert_eq!((p, r).intersection(), None);
+    let bad = Plane::new(Vector3::unit_y(), nan);
+    assert_eq!((bad, Ray3::new(Point3::new(0f64, 0f64, 0f64), Vector3::unit_y())).intersection(), None);
+    assert_eq!((bad, Line3::new(Point3::new(0f64, 0f64, 0f64), Point3::new(0f64, 5f64, 0f64))).intersection(), None);
+    assert!((bad, Plane::new(Vector3::unit_x(), 1f64)).intersection().is_none());
+    assert_eq!((bad, Plane::new(Vector3::unit_x(), 1f64), Plane::new(Vector3::unit_z(), 1f64)).intersection(), None);
+}
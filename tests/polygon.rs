@@ -0,0 +1,61 @@
+// Copyright 2015 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+extern crate collision;
+extern crate cgmath;
+
+use cgmath::{Point3, Vector3};
+use collision::{ConvexPolygon, Plane};
+
+fn square(z: f32) -> ConvexPolygon<f32> {
+    ConvexPolygon::new(vec![
+        Point3::new(-1.0, -1.0, z),
+        Point3::new(1.0, -1.0, z),
+        Point3::new(1.0, 1.0, z),
+        Point3::new(-1.0, 1.0, z),
+    ])
+}
+
+#[test]
+fn test_plane() {
+    let plane = square(2.0).plane().unwrap();
+    assert_eq!(plane.n, Vector3::new(0.0, 0.0, 1.0));
+    assert_eq!(plane.d, 2.0);
+    assert_eq!(square(2.0).centroid(), Point3::new(0.0, 0.0, 2.0));
+
+    let line = ConvexPolygon::new(vec![Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0), Point3::new(2.0, 0.0, 0.0)]);
+    assert_eq!(line.plane(), None);
+}
+
+#[test]
+fn test_clip() {
+    let clipped = square(0.0).clip_plane(&Plane::new(Vector3::new(-1.0, 0.0, 0.0), -0.5)).unwrap();
+    assert_eq!(clipped.points.len(), 4);
+    assert!(clipped.points.iter().all(|p| p.x <= 0.5));
+    assert!(clipped.points.contains(&Point3::new(0.5, -1.0, 0.0)));
+    assert!(clipped.points.contains(&Point3::new(0.5, 1.0, 0.0)));
+
+    // cutting off a corner leaves five points
+    let n = Vector3::new(-1.0f32, -1.0, 0.0) / 2.0f32.sqrt();
+    let clipped = square(0.0).clip_plane(&Plane::from_point_normal(Point3::new(0.5, 0.5, 0.0), n)).unwrap();
+    assert_eq!(clipped.points.len(), 5);
+
+    // touching the plane keeps the polygon whole
+    let clipped = square(0.0).clip_plane(&Plane::new(Vector3::new(-1.0, 0.0, 0.0), -1.0)).unwrap();
+    assert_eq!(clipped, square(0.0));
+
+    assert_eq!(square(0.0).clip_plane(&Plane::new(Vector3::new(1.0, 0.0, 0.0), 2.0)), None);
+}
@@ -0,0 +1,87 @@
+// Copyright 2015 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+extern crate collision;
+extern crate cgmath;
+
+use cgmath::{Point3, PerspectiveFov, Rad};
+use collision::{ConvexPolygon, PortalFrustum, PortalGraph, Projection, Relation};
+
+fn rect(x0: f32, x1: f32, y0: f32, y1: f32, z: f32) -> ConvexPolygon<f32> {
+    ConvexPolygon::new(vec![
+        Point3::new(x0, y0, z),
+        Point3::new(x1, y0, z),
+        Point3::new(x1, y1, z),
+        Point3::new(x0, y1, z),
+    ])
+}
+
+fn view() -> PortalFrustum<f32> {
+    let frustum = PerspectiveFov {
+        fovy: Rad(std::f32::consts::FRAC_PI_2),
+        aspect: 1.0,
+        near: 0.1,
+        far: 100.0,
+    }.to_frustum();
+    PortalFrustum::from_frustum(Point3::new(0.0, 0.0, 0.0), &frustum)
+}
+
+#[test]
+fn test_through() {
+    let view = view();
+    assert_eq!(view.contains(Point3::new(0.0, 0.0, -5.0)), Relation::In);
+
+    let narrowed = view.through(&rect(-1.0, 1.0, -1.0, 1.0, -10.0)).unwrap();
+    assert_eq!(narrowed.planes.len(), 5);
+    assert_eq!(narrowed.contains(Point3::new(0.0, 0.0, -20.0)), Relation::In);
+    assert_eq!(narrowed.contains(Point3::new(1.5, 0.0, -20.0)), Relation::In);
+    assert_eq!(narrowed.contains(Point3::new(2.5, 0.0, -20.0)), Relation::Out);
+    assert_eq!(narrowed.contains(Point3::new(0.0, 0.0, -5.0)), Relation::Out);
+    assert_eq!(narrowed.contains(Point3::new(0.0, 0.0, -200.0)), Relation::Out);
+
+    // a portal facing away from the eye works the same
+    let mut flipped = rect(-1.0, 1.0, -1.0, 1.0, -10.0);
+    flipped.points.reverse();
+    let narrowed = view.through(&flipped).unwrap();
+    assert_eq!(narrowed.contains(Point3::new(1.5, 0.0, -20.0)), Relation::In);
+    assert_eq!(narrowed.contains(Point3::new(2.5, 0.0, -20.0)), Relation::Out);
+    assert_eq!(narrowed.contains(Point3::new(0.0, 0.0, -5.0)), Relation::Out);
+
+    assert_eq!(view.through(&rect(20.0, 21.0, -1.0, 1.0, -10.0)), None);
+    assert_eq!(view.through(&rect(-1.0, 1.0, -1.0, 1.0, 10.0)), None);
+}
+
+#[test]
+fn test_graph() {
+    let mut graph = PortalGraph::new();
+    assert_eq!(graph, PortalGraph::default());
+    let cells: Vec<usize> = (0..4).map(|_| graph.add_cell()).collect();
+    graph.add_portal(cells[0], cells[1], rect(-1.0, 1.0, -1.0, 1.0, -10.0));
+    graph.add_portal(cells[1], cells[2], rect(5.0, 6.0, -1.0, 1.0, -20.0));
+    graph.add_portal(cells[1], cells[3], rect(1.0, 3.0, -1.0, 1.0, -20.0));
+    graph.add_portal(cells[3], cells[0], rect(40.0, 50.0, -1.0, 1.0, -30.0));
+    assert_eq!(graph.cell_count(), 4);
+    assert_eq!(graph.portals().len(), 4);
+
+    let visible = graph.visible(cells[0], &view());
+    let found: Vec<usize> = visible.iter().map(|v| v.0).collect();
+    assert_eq!(found, vec![0, 1, 3]);
+
+    let view = &visible[2].1;
+    assert_eq!(view.contains(Point3::new(1.875, 0.0, -25.0)), Relation::In);
+    assert_eq!(view.contains(Point3::new(0.0, 0.0, -25.0)), Relation::Out);
+    assert_eq!(view.contains(Point3::new(1.5, 0.0, -15.0)), Relation::Out);
+}
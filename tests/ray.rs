@@ -0,0 +1,57 @@
+// Copyright 2013-2014 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+extern crate collision;
+
+use cgmath::{Point3, Vector3};
+use collision::{Line, Line3, Ray, Ray3};
+
+#[test]
+fn test_point_at() {
+    let ray: Ray3<f32> = Ray::new(Point3::new(1.0, 2.0, 3.0), Vector3::new(0.0, 0.0, 1.0));
+    assert_eq!(ray.point_at(2.0), Point3::new(1.0, 2.0, 5.0));
+    assert_eq!(ray.closest_param(Point3::new(4.0, 2.0, 6.0)), 3.0);
+    assert_eq!(ray.closest_param(Point3::new(4.0, 2.0, 0.0)), 0.0);
+    assert_eq!(ray.closest_point(Point3::new(4.0, 2.0, 0.0)), ray.origin);
+    assert_eq!(ray.to_line(1.0, 2.0),
+               Line::new(Point3::new(1.0, 2.0, 4.0), Point3::new(1.0, 2.0, 5.0)));
+}
+
+#[test]
+fn test_closest_approach() {
+    let ray: Ray3<f32> = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+    let l0: Line3<f32> = Line::new(Point3::new(2.0, 1.0, -1.0), Point3::new(2.0, 1.0, 1.0));
+    let l1: Line3<f32> = Line::new(Point3::new(-2.0, 1.0, 1.0), Point3::new(-2.0, 1.0, 3.0));
+    let l2: Line3<f32> = Line::new(Point3::new(3.0, 0.0, 2.0), Point3::new(3.0, 0.0, 4.0));
+
+    assert_eq!(ray.closest_approach(&l0), (2.0, 0.5));
+    assert_eq!(ray.closest_approach(&l1), (0.0, 0.0));
+    assert_eq!(ray.closest_approach(&l2), (3.0, 0.0));
+}
+
+#[test]
+fn test_triangle() {
+    let a = Point3::new(0.0f32, 0.0, 0.0);
+    let b = Point3::new(4.0f32, 0.0, 0.0);
+    let c = Point3::new(0.0f32, 4.0, 0.0);
+    let r0: Ray3<f32> = Ray::new(Point3::new(1.0, 1.0, 2.0), Vector3::new(0.0, 0.0, -1.0));
+    let r1: Ray3<f32> = Ray::new(Point3::new(1.0, 1.0, 2.0), Vector3::new(0.0, 0.0, 1.0));
+    let r2: Ray3<f32> = Ray::new(Point3::new(1.0, 1.0, 2.0), Vector3::new(1.0, 0.0, 0.0));
+
+    assert_eq!(r0.intersect_triangle(a, b, c), Some(Point3::new(1.0, 1.0, 0.0)));
+    assert_eq!(r1.intersect_triangle(a, b, c), None);
+    assert_eq!(r2.intersect_triangle(a, b, c), None);
+}
@@ -0,0 +1,105 @@
+// Copyright 2015 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+
+extern crate cgmath;
+extern crate collision;
+
+use cgmath::*;
+use collision::*;
+
+fn set(spans: &[(f64, f64)]) -> SpanSet<f64> {
+    SpanSet::from_spans(spans.iter().map(|&(a, b)| Span::new(a, b)).collect())
+}
+
+fn assert_span(span: Option<Span<f64>>, enter: f64, exit: f64) {
+    let span = span.unwrap();
+    assert_ulps_eq!(span.enter, &enter);
+    assert_ulps_eq!(span.exit, &exit);
+}
+
+#[test]
+fn test_span_set() {
+    let a = set(&[(4.0, 6.0), (0.0, 2.0), (1.0, 3.0)]);
+    assert_eq!(a.spans(), &[Span::new(0.0, 3.0), Span::new(4.0, 6.0)]);
+    assert_eq!(a.first(), Some(0.0));
+    assert_eq!(a.length(), 5.0);
+
+    let b = set(&[(2.0, 5.0), (7.0, 8.0)]);
+    assert_eq!(a.union(&b), set(&[(0.0, 6.0), (7.0, 8.0)]));
+    assert_eq!(a.intersection(&b), set(&[(2.0, 3.0), (4.0, 5.0)]));
+    assert_eq!(a.difference(&b), set(&[(0.0, 2.0), (5.0, 6.0)]));
+    assert_eq!(b.difference(&a), set(&[(3.0, 4.0), (7.0, 8.0)]));
+    assert_eq!(a.difference(&a), SpanSet::new());
+    assert!(a.intersection(&SpanSet::new()).is_empty());
+    assert_eq!(SpanSet::<f32>::default(), SpanSet::new());
+    assert_eq!(set(&[(0.0, 10.0)]).difference(&set(&[(1.0, 2.0), (3.0, 4.0)])),
+               set(&[(0.0, 1.0), (2.0, 3.0), (4.0, 10.0)]));
+}
+
+#[test]
+fn test_ray_span() {
+    let ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vector3::unit_x());
+    let inside = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::unit_x() * 2.0);
+    let miss = Ray::new(Point3::new(-5.0, 3.0, 0.0), Vector3::unit_x());
+
+    let sphere = Sphere { center: Point3::new(0.0, 0.0, 0.0), radius: 1.0 };
+    assert_span(sphere.ray_span(&ray), 4.0, 6.0);
+    assert_span(sphere.ray_span(&inside), 0.0, 0.5);
+    assert_eq!(sphere.ray_span(&miss), None);
+
+    let aabb = Aabb3::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+    assert_span(aabb.ray_span(&ray), 4.0, 6.0);
+    assert_span(aabb.ray_span(&inside), 0.0, 0.5);
+    assert_eq!(aabb.ray_span(&miss), None);
+
+    let aabb2 = Aabb2::new(Point2::new(-1.0, -1.0), Point2::new(1.0, 1.0));
+    assert_span(aabb2.ray_span(&Ray::new(Point2::new(0.0, -3.0), Vector2::unit_y())), 2.0, 4.0);
+
+    let obb = Obb3::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, std::f64::consts::PI / 4.0),
+                        Vector3::new(1.0, 1.0, 1.0));
+    assert_span(obb.ray_span(&ray), 5.0 - 2.0f64.sqrt(), 5.0 + 2.0f64.sqrt());
+    assert_eq!(obb.ray_span(&miss), None);
+
+    let cylinder = Cylinder::new(Point3::new(0.0, 0.0, 0.0), Vector3::unit_z(), 1.0);
+    assert_span(cylinder.ray_span(&ray), 4.0, 6.0);
+    assert_span(cylinder.ray_span(&Ray::new(Point3::new(0.5, 0.0, 5.0), -Vector3::unit_z())), 4.0, 6.0);
+    assert_eq!(cylinder.ray_span(&miss), None);
+
+    let frustum = Frustum::from_matrix4(ortho(-1.0, 1.0, -1.0, 1.0, -1.0, 1.0)).unwrap();
+    assert_span(frustum.ray_span(&ray), 4.0, 6.0);
+    assert_eq!(frustum.ray_span(&miss), None);
+
+    let planes = vec![Plane::new(Vector3::unit_x(), -1.0), Plane::new(-Vector3::unit_x(), -1.0),
+                      Plane::new(Vector3::new(-1.0, 1.0, 0.0).normalize(), -1.0)];
+    let hull = ConvexPolyhedron::new(planes);
+    assert!(hull.contains(Point3::new(0.0, 0.0, 0.0)));
+    assert!(!hull.contains(Point3::new(0.0, -5.0, 0.0)));
+    assert_span(hull.ray_span(&ray), 4.0, 6.0);
+    assert_eq!(hull.ray_span(&Ray::new(Point3::new(0.0, -5.0, 0.0), -Vector3::unit_y())), None);
+}
+
+#[test]
+fn test_csg() {
+    // a sphere with a box cut out of it
+    let ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vector3::unit_x());
+    let sphere = Sphere { center: Point3::new(0.0, 0.0, 0.0), radius: 2.0 };
+    let aabb = Aabb3::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+    let spans = |s: Option<Span<f64>>| s.map(SpanSet::from).unwrap_or_else(SpanSet::new);
+    let hollow = spans(sphere.ray_span(&ray)).difference(&spans(aabb.ray_span(&ray)));
+    assert_eq!(hollow, set(&[(3.0, 4.0), (6.0, 7.0)]));
+    assert_eq!(hollow.length(), 2.0);
+}
@@ -52,3 +52,79 @@ fn test_bound() {
         Plane::from_point_normal(point + normal * 3.0, normal),
         ), Relation::Out);
 }
+
+#[test]
+fn test_intersection_degenerate() {
+    let sphere = Sphere { center: Point3::new(0f64, 0f64, 0f64), radius: 1f64 };
+
+    // starting inside, leaving through the surface in either direction
+    let r = Ray::new(Point3::new(0f64, 0f64, 0.5f64), Vector3::new(0f64, 0f64, 1f64));
+    assert_eq!((sphere, r).intersection(), Some(Point3::new(0f64, 0f64, 1f64)));
+    let r = Ray::new(Point3::new(0f64, 0f64, 0.5f64), Vector3::new(0f64, 0f64, -1f64));
+    assert_eq!((sphere, r).intersection(), Some(Point3::new(0f64, 0f64, -1f64)));
+
+    // unnormalized directions give the same point
+    let r = Ray::new(Point3::new(0f64, 0f64, 5f64), Vector3::new(0f64, 0f64, -2f64));
+    assert_eq!((sphere, r).intersection(), Some(Point3::new(0f64, 0f64, 1f64)));
+
+    // pointing away
+    let r = Ray::new(Point3::new(0f64, 0f64, 5f64), Vector3::new(0f64, 0f64, 1f64));
+    assert_eq!((sphere, r).intersection(), None);
+
+    // starting on the surface
+    let r = Ray::new(Point3::new(0f64, 0f64, 1f64), Vector3::new(0f64, 0f64, 1f64));
+    assert_eq!((sphere, r).intersection(), Some(Point3::new(0f64, 0f64, 1f64)));
+
+    // zero direction
+    let r = Ray::new(Point3::new(0f64, 0.5f64, 0f64), Vector3::new(0f64, 0f64, 0f64));
+    assert_eq!((sphere, r).intersection(), Some(Point3::new(0f64, 0.5f64, 0f64)));
+    let r = Ray::new(Point3::new(0f64, 5f64, 0f64), Vector3::new(0f64, 0f64, 0f64));
+    assert_eq!((sphere, r).intersection(), None);
+
+    // zero radius
+    let point = Sphere { center: Point3::new(0f64, 0f64, 0f64), radius: 0f64 };
+    let r = Ray::new(Point3::new(0f64, 0f64, 5f64), Vector3::new(0f64, 0f64, -1f64));
+    assert_eq!((point, r).intersection(), Some(Point3::new(0f64, 0f64, 0f64)));
+
+    // negative radius and NaN
+    let nan = std::f64::NAN;
+    let bad = Sphere { center: Point3::new(0f64, 0f64, 0f64), radius: -1f64 };
+    assert_eq!((bad, r).intersection(), None);
+    let bad = Sphere { center: Point3::new(0f64, 0f64, 0f64), radius: nan };
+    assert_eq!((bad, r).intersection(), None);
+    let r = Ray::new(Point3::new(0f64, nan, 5f64), Vector3::new(0f64, 0f64, -1f64));
+    assert_eq!((sphere, r).intersection(), None);
+    let l = Line::new(Point3::new(0f64, 0f64, 5f64), Point3::new(0f64, nan, -5f64));
+    assert_eq!((sphere, l).intersection(), None);
+
+    // zero length segments
+    let p = Point3::new(0f64, 0.5f64, 0f64);
+    assert_eq!((sphere, Line::new(p, p)).intersection(), Some(p));
+    let p = Point3::new(0f64, 5f64, 0f64);
+    assert_eq!((sphere, Line::new(p, p)).intersection(), None);
+}
+
+#[test]
+fn test_relate() {
+    let sphere = |x: f64, r: f64| Sphere { center: Point3::new(x, 0.0, 0.0), radius: r };
+    let outer = sphere(0.0, 3.0);
+    assert_eq!(sphere(1.0, 1.0).relate(&outer), Relation::In);
+    assert_eq!(sphere(2.0, 1.0).relate(&outer), Relation::Cross);
+    assert_eq!(sphere(4.0, 1.0).relate(&outer), Relation::Cross);
+    assert_eq!(sphere(5.0, 1.0).relate(&outer), Relation::Out);
+    assert_eq!(outer.relate(&sphere(1.0, 1.0)), Relation::Cross);
+
+    let aabb = Aabb3::new(Point3::new(-2.0, -2.0, -2.0), Point3::new(2.0, 2.0, 2.0));
+    assert_eq!(sphere(0.0, 1.0).relate(&aabb), Relation::In);
+    assert_eq!(sphere(1.5, 1.0).relate(&aabb), Relation::Cross);
+    assert_eq!(sphere(3.5, 1.0).relate(&aabb), Relation::Out);
+
+    assert_eq!(Point3::new(1.0, 0.0, 0.0).relate(&outer), Relation::In);
+    assert_eq!(Point3::new(0.0, 3.0, 0.0).relate(&outer), Relation::Cross);
+    assert_eq!(Point3::new(0.0, 3.0, 1.0).relate(&outer), Relation::Out);
+
+    let frustum = Frustum::from_matrix4(ortho(-10.0, 10.0, -10.0, 10.0, -10.0, 10.0)).unwrap();
+    assert_eq!(sphere(0.0, 1.0).relate(&frustum), Relation::In);
+    assert_eq!(sphere(10.0, 1.0).relate(&frustum), Relation::Cross);
+    assert_eq!(Point3::new(0.0, 0.0, 20.0).relate(&frustum), Relation::Out);
+}